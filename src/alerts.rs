@@ -0,0 +1,192 @@
+use crate::config::AlertConfig;
+use crate::state::AppState;
+
+/// Conditions the monitor can alert on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlertKind {
+    LowPeers,
+    HighLatency,
+    DiskFull,
+    ServiceDown,
+    BlockBehind,
+    StaleBlock,
+    SyncBehind,
+}
+
+impl AlertKind {
+    pub fn name(self) -> &'static str {
+        match self {
+            AlertKind::LowPeers => "low_peers",
+            AlertKind::HighLatency => "high_latency",
+            AlertKind::DiskFull => "disk_full",
+            AlertKind::ServiceDown => "service_down",
+            AlertKind::BlockBehind => "block_behind",
+            AlertKind::StaleBlock => "stale_block",
+            AlertKind::SyncBehind => "sync_behind",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Alert {
+    pub kind: AlertKind,
+    pub message: String,
+    /// Observed value and the threshold it crossed
+    pub value: f64,
+    pub threshold: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Transition {
+    Fired,
+    Resolved,
+}
+
+/// An alert entering or leaving the firing state
+#[derive(Debug, Clone)]
+pub struct AlertEvent {
+    pub alert: Alert,
+    pub transition: Transition,
+}
+
+/// Currently firing alerts; only transitions in and out of the alert state are reported
+#[derive(Debug, Default)]
+pub struct Alerts {
+    active: Vec<Alert>,
+}
+
+impl Alerts {
+    /// Replace the active set with `current` and return what fired or resolved
+    pub fn update(&mut self, current: Vec<Alert>) -> Vec<AlertEvent> {
+        let fired = current
+            .iter()
+            .filter(|alert| !self.active.iter().any(|a| a.kind == alert.kind))
+            .map(|alert| AlertEvent { alert: alert.clone(), transition: Transition::Fired });
+        let resolved = self
+            .active
+            .iter()
+            .filter(|alert| !current.iter().any(|a| a.kind == alert.kind))
+            .map(|alert| AlertEvent { alert: alert.clone(), transition: Transition::Resolved });
+        let events = fired.chain(resolved).collect();
+        self.active = current;
+        events
+    }
+
+    pub fn active(&self) -> &[Alert] {
+        &self.active
+    }
+}
+
+/// Check every configured threshold against the current state
+pub fn evaluate(state: &AppState, config: &AlertConfig) -> Vec<Alert> {
+    let mut alerts = Vec::new();
+    if !config.enabled {
+        return alerts;
+    }
+
+    // Node metrics are only checked once the endpoint has answered
+    let metrics = &state.metrics;
+    if metrics.block_num > 0 {
+        if metrics.peer_count < config.min_peers {
+            alerts.push(Alert {
+                kind: AlertKind::LowPeers,
+                message: format!("peers {} < {}", metrics.peer_count, config.min_peers),
+                value: metrics.peer_count as f64,
+                threshold: config.min_peers as f64,
+            });
+        }
+        if metrics.latency_p99_ms > config.max_latency_ms {
+            alerts.push(Alert {
+                kind: AlertKind::HighLatency,
+                message: format!("p99 latency {}ms > {}ms", metrics.latency_p99_ms, config.max_latency_ms),
+                value: metrics.latency_p99_ms as f64,
+                threshold: config.max_latency_ms as f64,
+            });
+        }
+        let sync_pct = metrics.sync_percentage();
+        if sync_pct < config.min_sync_pct {
+            alerts.push(Alert {
+                kind: AlertKind::SyncBehind,
+                message: format!("statesync {:.1}% < {:.1}%", sync_pct, config.min_sync_pct),
+                value: sync_pct,
+                threshold: config.min_sync_pct,
+            });
+        }
+    }
+
+    if let Some(age) = state.time_since_last_block() {
+        if age.as_secs() > config.max_block_age_secs {
+            alerts.push(Alert {
+                kind: AlertKind::StaleBlock,
+                message: format!("no block for {}s (> {}s)", age.as_secs(), config.max_block_age_secs),
+                value: age.as_secs_f64(),
+                threshold: config.max_block_age_secs as f64,
+            });
+        }
+    }
+
+    let sys = &state.system;
+    if sys.disk_data_available && sys.disk_used_pct > config.max_disk_pct {
+        alerts.push(Alert {
+            kind: AlertKind::DiskFull,
+            message: format!("disk {:.0}% > {:.0}%", sys.disk_used_pct, config.max_disk_pct),
+            value: sys.disk_used_pct,
+            threshold: config.max_disk_pct,
+        });
+    }
+
+    if config.services && sys.services_available {
+        let down: Vec<&str> = sys
+            .services
+            .iter()
+            .filter(|(_, active)| !active)
+            .map(|(unit, _)| unit.as_str())
+            .collect();
+        if !down.is_empty() {
+            alerts.push(Alert {
+                kind: AlertKind::ServiceDown,
+                message: format!("{} down", down.join(", ")),
+                value: down.len() as f64,
+                threshold: 0.0,
+            });
+        }
+    }
+
+    let block_diff = sys.block_difference(state.block_height());
+    if block_diff > config.max_block_diff {
+        alerts.push(Alert {
+            kind: AlertKind::BlockBehind,
+            message: format!("{} blocks behind network (> {})", block_diff, config.max_block_diff),
+            value: block_diff as f64,
+            threshold: config.max_block_diff as f64,
+        });
+    }
+
+    alerts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn alert(kind: AlertKind) -> Alert {
+        Alert { kind, message: String::new(), value: 0.0, threshold: 0.0 }
+    }
+
+    #[test]
+    fn test_update_reports_only_transitions() {
+        let mut alerts = Alerts::default();
+
+        let events = alerts.update(vec![alert(AlertKind::LowPeers)]);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].transition, Transition::Fired);
+
+        // Still firing: no new events
+        assert!(alerts.update(vec![alert(AlertKind::LowPeers)]).is_empty());
+
+        let events = alerts.update(vec![alert(AlertKind::DiskFull)]);
+        assert_eq!(events.len(), 2);
+        assert!(events.iter().any(|e| e.alert.kind == AlertKind::DiskFull && e.transition == Transition::Fired));
+        assert!(events.iter().any(|e| e.alert.kind == AlertKind::LowPeers && e.transition == Transition::Resolved));
+    }
+}
@@ -0,0 +1,21 @@
+use anyhow::{anyhow, Result};
+
+/// System clipboard, opened on first use. The handle is kept for the whole session
+/// because on X11 the copied text disappears once its owner is dropped.
+#[derive(Default)]
+pub struct Clipboard {
+    inner: Option<arboard::Clipboard>,
+}
+
+impl Clipboard {
+    /// Copy `text`, failing on headless machines (SSH without a display, servers)
+    pub fn copy(&mut self, text: &str) -> Result<()> {
+        let clipboard = match &mut self.inner {
+            Some(clipboard) => clipboard,
+            None => self
+                .inner
+                .insert(arboard::Clipboard::new().map_err(|e| anyhow!("no clipboard: {}", e))?),
+        };
+        clipboard.set_text(text).map_err(|e| anyhow!("clipboard: {}", e))
+    }
+}
@@ -0,0 +1,750 @@
+use anyhow::{bail, Context, Result};
+use ratatui::style::Color;
+use serde::{Deserialize, Deserializer};
+use std::fs;
+
+use crate::metrics::MetricsAuth;
+use crate::state::Theme;
+
+const USAGE: &str = "\
+Usage: monad-monitor [OPTIONS]
+
+Options:
+  -c, --config <path>   Load settings from a TOML config file
+      --service <unit>  Systemd unit to monitor (repeatable, replaces the default list)
+      --triedb-path <path>
+                        Storage device passed to monad-mpt [default: /dev/triedb]
+      --rpc-url <url>   Node RPC endpoint; ws:// and wss:// subscribe to new blocks,
+                        http:// and https:// poll for them [default: ws://localhost:8081]
+      --network <name>  Network the node is on (mainnet, testnet), which picks the default
+                        reference RPC [default: mainnet]
+      --metrics-auth-bearer <token>
+                        Bearer token sent to metrics endpoints (overrides MONAD_METRICS_AUTH;
+                        visible in `ps`, so prefer the variable on shared hosts)
+      --external-rpc <url>
+                        Reference RPC (ws://, wss://, http:// or https://) for the block
+                        difference [default: the network's public endpoint]
+      --confirm-quit    Ask before quitting on q (Esc still quits immediately)
+      --no-color        Monochrome output using only bold / dim / reverse (also NO_COLOR)
+      --theme <name>    Start with this theme (gray, light, monad, matrix, ocean, christmas,
+                        dracula, solarized, high-contrast, custom) [default: gray]
+      --compact         Start in the minimal two-line layout (toggle with c)
+      --tps-window <n>  Readings kept for the TPS, latency and pool graphs [default: 300]
+      --metrics-interval <ms>
+                        Time between metrics scrapes and HTTP RPC polls [default: 1000]
+      --system-interval <ms>
+                        Time between system stat refreshes [default: 5000]
+      --persist-history Save the TPS / latency history and peak, and reload it on the next start
+      --log-csv <path>  Append a row of key metrics to a CSV file on each refresh
+      --log-jsonl <path>
+                        Append a JSON snapshot of the full state to a file on each refresh
+      --serve-json <addr>
+                        Serve the current state as JSON at http://<addr>/state
+      --prometheus-export <addr>
+                        Serve derived metrics for Prometheus at http://<addr>/metrics
+                        (alias: --serve-metrics)
+      --webhook-url <url>
+                        POST a JSON payload here when an alert fires or resolves
+      --record <dir>    Save every fetched update to <dir>/updates.jsonl for later replay
+      --replay <dir>    Play back updates saved with --record instead of contacting the node
+      --replay-speed <x>
+                        Play the recording back this many times faster [default: 1]
+  -h, --help            Print help
+  -V, --version         Print version
+
+Environment:
+  MONAD_RPC_AUTH        Authorization header value (e.g. \"Bearer <token>\") sent to RPC endpoints
+  MONAD_METRICS_AUTH    Authorization header value (e.g. \"Basic <base64>\") sent to metrics endpoints";
+
+/// Shortest refresh interval accepted on the command line or reached with `+`
+pub const MIN_REFRESH_INTERVAL_MS: u64 = 250;
+
+/// Runtime configuration, loaded from an optional TOML file and CLI flags
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    /// Systemd units whose status is shown (the first one is used for uptime)
+    pub services: Vec<String>,
+    /// Storage device passed to `monad-mpt --storage`
+    pub triedb_path: String,
+    /// Node RPC endpoint (WebSocket subscribes, HTTP polls)
+    pub rpc_url: String,
+    /// Network the node is on, shown in the header
+    pub network: Network,
+    /// Reference RPC endpoint for the block difference (the network's public RPC when unset)
+    pub external_rpc: Option<String>,
+    /// Authorization header value sent to RPC endpoints (overridden by MONAD_RPC_AUTH)
+    pub rpc_auth: Option<String>,
+    /// Authorization header value sent to metrics endpoints (overridden by MONAD_METRICS_AUTH)
+    pub metrics_auth: Option<String>,
+    /// Bearer token sent to metrics endpoints when `metrics_auth` is unset
+    pub metrics_auth_bearer: Option<String>,
+    /// Basic auth user (and optional password) for metrics endpoints, when neither of
+    /// the above is set
+    pub metrics_basic_user: Option<String>,
+    pub metrics_basic_password: Option<String>,
+    /// Append a CSV row of key metrics here on each refresh
+    pub log_csv: Option<String>,
+    /// Append a JSON line with the full state snapshot here on each refresh
+    pub log_jsonl: Option<String>,
+    /// Address (e.g. 127.0.0.1:9100) to serve the state snapshot as JSON at /state
+    pub serve_json: Option<String>,
+    /// Address to serve derived metrics in Prometheus text format at /metrics
+    pub prometheus_export: Option<String>,
+    /// POST alert transitions as JSON to this URL
+    pub webhook_url: Option<String>,
+    /// Directory to save every fetched update to, for `replay`
+    pub record: Option<String>,
+    /// Directory of a recording to play back instead of fetching live data
+    pub replay: Option<String>,
+    /// Playback rate of `replay` (2 = twice as fast)
+    pub replay_speed: f64,
+    /// Theme to start with, by name (see `Theme::from_name`)
+    pub theme: Option<String>,
+    /// Start in the minimal two-line layout
+    pub compact: bool,
+    /// Ask for confirmation before `q` quits
+    pub confirm_quit: bool,
+    /// Ignore the theme and render with text attributes only
+    pub no_color: bool,
+    /// Keep TPS / latency history across restarts in the user's state directory
+    pub persist_history: bool,
+    /// Give up on an HTTP request or WebSocket connect after this many seconds
+    pub request_timeout_secs: u64,
+    /// Milliseconds between metrics scrapes and HTTP RPC polls (+ / - change it at runtime)
+    pub metrics_interval_ms: u64,
+    /// Milliseconds between system stat refreshes (scaled along with the metrics one)
+    pub system_interval_ms: u64,
+    /// Weight of the newest reading in the smoothed headline TPS (1 = no smoothing)
+    pub tps_smoothing: f64,
+    /// Readings kept for the TPS, latency and pool graphs (one per metrics scrape)
+    pub tps_window: usize,
+    pub thresholds: Thresholds,
+    pub alerts: AlertConfig,
+    pub graph: GraphConfig,
+    /// Prometheus metric names read from the node, for builds that rename them
+    pub metric_names: MetricNames,
+    /// Operator-defined palette, added to the theme cycle as "custom"
+    pub custom_theme: Option<CustomTheme>,
+    /// Further nodes shown next to the local one (`n` switches which one is detailed)
+    pub nodes: Vec<NodeConfig>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            services: vec![
+                "monad-bft".to_string(),
+                "monad-execution".to_string(),
+                "monad-rpc".to_string(),
+            ],
+            triedb_path: "/dev/triedb".to_string(),
+            rpc_url: "ws://localhost:8081".to_string(),
+            network: Network::default(),
+            external_rpc: None,
+            rpc_auth: None,
+            metrics_auth: None,
+            metrics_auth_bearer: None,
+            metrics_basic_user: None,
+            metrics_basic_password: None,
+            log_csv: None,
+            log_jsonl: None,
+            serve_json: None,
+            prometheus_export: None,
+            webhook_url: None,
+            record: None,
+            replay: None,
+            replay_speed: 1.0,
+            theme: None,
+            compact: false,
+            confirm_quit: false,
+            no_color: false,
+            persist_history: false,
+            request_timeout_secs: 5,
+            metrics_interval_ms: 1000,
+            system_interval_ms: 5000,
+            tps_smoothing: 0.2,
+            tps_window: 300,
+            thresholds: Thresholds::default(),
+            alerts: AlertConfig::default(),
+            graph: GraphConfig::default(),
+            metric_names: MetricNames::default(),
+            custom_theme: None,
+            nodes: Vec::new(),
+        }
+    }
+}
+
+/// Health thresholds used to color stats (green / yellow / red)
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct Thresholds {
+    /// Upstream validator count below which the VAL stat turns yellow
+    pub validators_warn: u64,
+    /// Upstream validator count below which the VAL stat turns red
+    pub validators_crit: u64,
+    /// Pending pool depth above which the POOL stat turns yellow
+    pub pool_warn: u64,
+    /// Pending pool depth above which the POOL stat turns red
+    pub pool_crit: u64,
+    /// Swap usage percentage above which the SWAP stat turns red
+    pub swap_crit_pct: f64,
+    /// Verified-minus-finalized gap (blocks) above which the VER stat turns yellow
+    pub verified_gap_warn: u64,
+    /// Verified-minus-finalized gap (blocks) above which the VER stat turns red
+    pub verified_gap_crit: u64,
+    /// Blocks away from the external reference before a full-width banner is shown
+    pub behind_banner_blocks: u64,
+    /// Retained history (in blocks) below which the HIST stat turns yellow
+    pub history_min_blocks: u64,
+    /// Seconds between consecutive blocks above which the GAP column turns red
+    pub block_gap_crit_secs: u64,
+    /// Seconds without a new block after which the header's block-age gauge flashes red
+    pub block_age_crit_secs: u64,
+}
+
+impl Default for Thresholds {
+    fn default() -> Self {
+        Self {
+            validators_warn: 10,
+            validators_crit: 1,
+            pool_warn: 5_000,
+            pool_crit: 20_000,
+            swap_crit_pct: 10.0,
+            verified_gap_warn: 3,
+            verified_gap_crit: 10,
+            behind_banner_blocks: 50,
+            history_min_blocks: 100_000,
+            block_gap_crit_secs: 2,
+            block_age_crit_secs: 5,
+        }
+    }
+}
+
+/// Conditions that raise an alert banner (and ring the terminal bell)
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct AlertConfig {
+    pub enabled: bool,
+    /// Ring the terminal bell when a new alert fires
+    pub bell: bool,
+    /// Ring again every this many seconds while alerts stay unacknowledged (0 = only when one fires)
+    pub bell_interval_secs: u64,
+    /// Alert when the peer count drops below this
+    pub min_peers: u64,
+    /// Alert when p99 latency exceeds this many milliseconds
+    pub max_latency_ms: u64,
+    /// Alert when triedb disk usage exceeds this percentage
+    pub max_disk_pct: f64,
+    /// Alert when any monitored service is down
+    pub services: bool,
+    /// Alert when the node is more than this many blocks behind the network
+    pub max_block_diff: i64,
+    /// Alert when no new block has been seen for this many seconds (blocks normally
+    /// arrive well under a second apart, so 10s already means a stall)
+    pub max_block_age_secs: u64,
+    /// Alert while statesync progress is below this percentage (0 disables)
+    pub min_sync_pct: f64,
+}
+
+impl Default for AlertConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            bell: true,
+            bell_interval_secs: 60,
+            min_peers: 5,
+            max_latency_ms: 2_000,
+            max_disk_pct: 90.0,
+            services: true,
+            max_block_diff: 10,
+            max_block_age_secs: 10,
+            min_sync_pct: 0.0,
+        }
+    }
+}
+
+/// Monad network the node belongs to
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Network {
+    #[default]
+    Mainnet,
+    Testnet,
+}
+
+impl Network {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "mainnet" => Some(Network::Mainnet),
+            "testnet" => Some(Network::Testnet),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Network::Mainnet => "mainnet",
+            Network::Testnet => "testnet",
+        }
+    }
+
+    /// Public RPC the block difference is measured against unless `external_rpc` is set
+    pub fn default_external_rpc(self) -> &'static str {
+        match self {
+            Network::Mainnet => "wss://rpc-mainnet.monadinfra.com",
+            Network::Testnet => "https://testnet-rpc.monad.xyz",
+        }
+    }
+}
+
+/// Graph panel options
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct GraphConfig {
+    /// Plot latency on a log scale so spikes don't flatten the baseline
+    pub latency_log_scale: bool,
+}
+
+/// Prometheus metric name behind each parsed value
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct MetricNames {
+    pub block_num: String,
+    pub tx_commits: String,
+    pub peer_count: String,
+    pub statesync_progress: String,
+    pub statesync_target: String,
+    pub uptime: String,
+    /// Pre-computed p99 gauge, in milliseconds
+    pub latency_p99: String,
+    /// Base name of the latency summary or histogram, in milliseconds
+    pub latency: String,
+    pub pending_txs: String,
+    pub upstream_validators: String,
+    pub consensus_round: String,
+    pub consensus_view: String,
+}
+
+impl Default for MetricNames {
+    fn default() -> Self {
+        Self {
+            block_num: "monad_execution_ledger_block_num".to_string(),
+            tx_commits: "monad_execution_ledger_num_tx_commits".to_string(),
+            peer_count: "monad_peer_disc_num_peers".to_string(),
+            statesync_progress: "monad_statesync_progress_estimate".to_string(),
+            statesync_target: "monad_statesync_last_target".to_string(),
+            uptime: "monad_total_uptime_us".to_string(),
+            latency_p99: "monad_bft_raptorcast_udp_secondary_broadcast_latency_p99_ms".to_string(),
+            latency: "monad_bft_raptorcast_udp_secondary_broadcast_latency_ms".to_string(),
+            pending_txs: "monad_bft_txpool_pool_tracked_txs".to_string(),
+            upstream_validators: "monad_peer_disc_num_upstream_validators".to_string(),
+            consensus_round: "monad_bft_consensus_round".to_string(),
+            consensus_view: "monad_bft_consensus_view".to_string(),
+        }
+    }
+}
+
+/// A remote node watched through its RPC and metrics endpoints; host stats (CPU,
+/// disk, services) are only available for the local node
+#[derive(Debug, Clone, Deserialize)]
+pub struct NodeConfig {
+    /// Label shown in place of the hostname
+    pub name: String,
+    /// RPC endpoint (WebSocket subscribes, HTTP polls)
+    pub rpc_url: String,
+    /// Prometheus metrics endpoint, e.g. http://10.0.0.2:8889/metrics
+    pub metrics_url: String,
+}
+
+/// Colors for the custom theme, given as "#RRGGBB" hex strings in the config file
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct CustomTheme {
+    #[serde(deserialize_with = "hex_color")]
+    pub title: Color,
+    #[serde(deserialize_with = "hex_color")]
+    pub label: Color,
+    #[serde(deserialize_with = "hex_color")]
+    pub value: Color,
+    #[serde(deserialize_with = "hex_color")]
+    pub text_dim: Color,
+    #[serde(deserialize_with = "hex_color")]
+    pub sparkline: Color,
+}
+
+fn hex_color<'de, D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Color, D::Error> {
+    let value = String::deserialize(deserializer)?;
+    parse_hex_color(&value)
+        .ok_or_else(|| serde::de::Error::custom(format!("invalid color {:?}, expected #RRGGBB", value)))
+}
+
+/// Parse "#6E54FF" (the # is optional) into an RGB color
+pub fn parse_hex_color(value: &str) -> Option<Color> {
+    let hex = value.strip_prefix('#').unwrap_or(value);
+    if hex.len() != 6 || !hex.is_ascii() {
+        return None;
+    }
+    let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+    Some(Color::Rgb(channel(0)?, channel(2)?, channel(4)?))
+}
+
+/// What `main` should do after parsing the command line
+pub enum Command {
+    Run(Box<Config>),
+    Help,
+    Version,
+}
+
+impl Config {
+    pub fn load(path: &str) -> Result<Self> {
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read config file {}", path))?;
+        toml::from_str(&contents).with_context(|| format!("Failed to parse config file {}", path))
+    }
+
+    /// Parse CLI arguments (excluding the program name)
+    pub fn from_args<I: IntoIterator<Item = String>>(args: I) -> Result<Command> {
+        let mut config_path: Option<String> = None;
+        let mut services: Vec<String> = Vec::new();
+        let mut triedb_path: Option<String> = None;
+        let mut rpc_url: Option<String> = None;
+        let mut theme: Option<String> = None;
+        let mut compact = false;
+        let mut no_color = false;
+        let mut confirm_quit = false;
+        let mut persist_history = false;
+        let mut tps_window: Option<usize> = None;
+        let mut metrics_interval_ms: Option<u64> = None;
+        let mut system_interval_ms: Option<u64> = None;
+        let mut network: Option<Network> = None;
+        let mut external_rpc: Option<String> = None;
+        let mut metrics_auth_bearer: Option<String> = None;
+        let mut log_csv: Option<String> = None;
+        let mut log_jsonl: Option<String> = None;
+        let mut serve_json: Option<String> = None;
+        let mut prometheus_export: Option<String> = None;
+        let mut webhook_url: Option<String> = None;
+        let mut record: Option<String> = None;
+        let mut replay: Option<String> = None;
+        let mut replay_speed: Option<f64> = None;
+
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "-c" | "--config" => config_path = Some(next_value(&mut args, &arg)?),
+                "--service" => services.push(next_value(&mut args, &arg)?),
+                "--triedb-path" => triedb_path = Some(next_value(&mut args, &arg)?),
+                "--theme" => theme = Some(next_value(&mut args, &arg)?),
+                "--compact" => compact = true,
+                "--no-color" => no_color = true,
+                "--confirm-quit" => confirm_quit = true,
+                "--persist-history" => persist_history = true,
+                "--tps-window" => {
+                    let value = next_value(&mut args, &arg)?;
+                    let window = value
+                        .parse()
+                        .ok()
+                        .filter(|&n: &usize| n > 0)
+                        .with_context(|| format!("Invalid value for --tps-window: {}", value))?;
+                    tps_window = Some(window);
+                }
+                "--metrics-interval" => metrics_interval_ms = Some(interval_ms(&mut args, &arg)?),
+                "--system-interval" => system_interval_ms = Some(interval_ms(&mut args, &arg)?),
+                "--rpc-url" => rpc_url = Some(next_value(&mut args, &arg)?),
+                "--network" => {
+                    let value = next_value(&mut args, &arg)?;
+                    let parsed = Network::from_name(&value)
+                        .with_context(|| format!("Unknown network: {} (valid networks: mainnet, testnet)", value))?;
+                    network = Some(parsed);
+                }
+                "--external-rpc" => external_rpc = Some(next_value(&mut args, &arg)?),
+                "--metrics-auth-bearer" => metrics_auth_bearer = Some(next_value(&mut args, &arg)?),
+                "--log-csv" => log_csv = Some(next_value(&mut args, &arg)?),
+                "--log-jsonl" => log_jsonl = Some(next_value(&mut args, &arg)?),
+                "--serve-json" => serve_json = Some(next_value(&mut args, &arg)?),
+                "--prometheus-export" | "--serve-metrics" => prometheus_export = Some(next_value(&mut args, &arg)?),
+                "--webhook-url" => webhook_url = Some(next_value(&mut args, &arg)?),
+                "--record" => record = Some(next_value(&mut args, &arg)?),
+                "--replay" => replay = Some(next_value(&mut args, &arg)?),
+                "--replay-speed" => {
+                    let value = next_value(&mut args, &arg)?;
+                    let speed = value
+                        .parse()
+                        .ok()
+                        .with_context(|| format!("Invalid value for --replay-speed: {}", value))?;
+                    replay_speed = Some(speed);
+                }
+                "-h" | "--help" => return Ok(Command::Help),
+                "-V" | "--version" => return Ok(Command::Version),
+                _ => bail!("Unknown argument: {}\n\n{}", arg, USAGE),
+            }
+        }
+
+        let mut config = match config_path {
+            Some(path) => Config::load(&path)?,
+            None => Config::default(),
+        };
+
+        // CLI flags override the config file
+        if !services.is_empty() {
+            config.services = services;
+        }
+        if let Some(path) = triedb_path {
+            config.triedb_path = path;
+        }
+        if theme.is_some() {
+            config.theme = theme;
+        }
+        if let Some(name) = config.theme.as_deref() {
+            match Theme::from_name(name) {
+                Some(Theme::Custom) if config.custom_theme.is_none() => {
+                    bail!("Theme \"custom\" needs a [custom_theme] section in the config file")
+                }
+                Some(_) => {}
+                None => bail!("Unknown theme: {} (valid themes: {})", name, Theme::names()),
+            }
+        }
+        if compact {
+            config.compact = true;
+        }
+        if confirm_quit {
+            config.confirm_quit = true;
+        }
+        if persist_history {
+            config.persist_history = true;
+        }
+        if let Some(window) = tps_window {
+            config.tps_window = window;
+        }
+        if let Some(ms) = metrics_interval_ms {
+            config.metrics_interval_ms = ms;
+        }
+        if let Some(ms) = system_interval_ms {
+            config.system_interval_ms = ms;
+        }
+        // https://no-color.org: any non-empty NO_COLOR disables color
+        if no_color || std::env::var("NO_COLOR").is_ok_and(|v| !v.is_empty()) {
+            config.no_color = true;
+        }
+        if let Some(url) = rpc_url {
+            config.rpc_url = url;
+        }
+        if let Some(network) = network {
+            config.network = network;
+        }
+        if external_rpc.is_some() {
+            config.external_rpc = external_rpc;
+        }
+        // Read from the environment rather than a flag so the secret stays out of `ps`
+        if let Some(auth) = std::env::var("MONAD_RPC_AUTH").ok().filter(|a| !a.is_empty()) {
+            config.rpc_auth = Some(auth);
+        }
+        if let Some(auth) = std::env::var("MONAD_METRICS_AUTH").ok().filter(|a| !a.is_empty()) {
+            config.metrics_auth = Some(auth);
+        }
+        // An explicit token on the command line beats any header from the file or environment
+        if metrics_auth_bearer.is_some() {
+            config.metrics_auth = None;
+            config.metrics_auth_bearer = metrics_auth_bearer;
+        }
+        if config.metrics_basic_password.is_some() && config.metrics_basic_user.is_none() {
+            bail!("metrics_basic_password needs metrics_basic_user");
+        }
+        if log_csv.is_some() {
+            config.log_csv = log_csv;
+        }
+        if log_jsonl.is_some() {
+            config.log_jsonl = log_jsonl;
+        }
+        if serve_json.is_some() {
+            config.serve_json = serve_json;
+        }
+        if prometheus_export.is_some() {
+            config.prometheus_export = prometheus_export;
+        }
+        if webhook_url.is_some() {
+            config.webhook_url = webhook_url;
+        }
+        if record.is_some() {
+            config.record = record;
+        }
+        if replay.is_some() {
+            config.replay = replay;
+        }
+        if let Some(speed) = replay_speed {
+            config.replay_speed = speed;
+        }
+        // Checked after merging, since the config file can set it too
+        if !(config.replay_speed > 0.0 && config.replay_speed.is_finite()) {
+            bail!("Invalid replay speed: {} (must be greater than 0)", config.replay_speed);
+        }
+        if config.record.is_some() && config.replay.is_some() {
+            bail!("--record and --replay can't be used together");
+        }
+
+        Ok(Command::Run(Box::new(config)))
+    }
+
+    /// Credentials for metrics endpoints: the raw header, else the bearer token, else
+    /// basic auth
+    pub fn metrics_auth(&self) -> Option<MetricsAuth> {
+        if let Some(header) = &self.metrics_auth {
+            return Some(MetricsAuth::Header(header.clone()));
+        }
+        if let Some(token) = &self.metrics_auth_bearer {
+            return Some(MetricsAuth::Bearer(token.clone()));
+        }
+        self.metrics_basic_user.as_ref().map(|user| MetricsAuth::Basic {
+            user: user.clone(),
+            password: self.metrics_basic_password.clone(),
+        })
+    }
+
+    /// Reference RPC for the block difference: `external_rpc`, else the network's public one
+    pub fn external_rpc_url(&self) -> String {
+        self.external_rpc
+            .clone()
+            .unwrap_or_else(|| self.network.default_external_rpc().to_string())
+    }
+
+    pub fn usage() -> &'static str {
+        USAGE
+    }
+}
+
+fn next_value<I: Iterator<Item = String>>(args: &mut I, flag: &str) -> Result<String> {
+    args.next()
+        .with_context(|| format!("Missing value for {}", flag))
+}
+
+/// Next argument as a refresh interval in milliseconds, at least MIN_REFRESH_INTERVAL_MS
+fn interval_ms<I: Iterator<Item = String>>(args: &mut I, flag: &str) -> Result<u64> {
+    let value = next_value(args, flag)?;
+    value
+        .parse()
+        .ok()
+        .filter(|&ms: &u64| ms >= MIN_REFRESH_INTERVAL_MS)
+        .with_context(|| format!("Invalid value for {}: {} (minimum {}ms)", flag, value, MIN_REFRESH_INTERVAL_MS))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_hex_color() {
+        assert_eq!(parse_hex_color("#6E54FF"), Some(Color::Rgb(110, 84, 255)));
+        assert_eq!(parse_hex_color("6e54ff"), Some(Color::Rgb(110, 84, 255)));
+        assert_eq!(parse_hex_color("#6E54F"), None);
+        assert_eq!(parse_hex_color("#GG54FF"), None);
+    }
+
+    #[test]
+    fn test_custom_theme_from_toml() {
+        let config: Config = toml::from_str(
+            r##"
+            [custom_theme]
+            title = "#6E54FF"
+            label = "#A0A0A0"
+            value = "#FFFFFF"
+            text_dim = "#808080"
+            sparkline = "#00FF00"
+            "##,
+        )
+        .unwrap();
+        assert_eq!(config.custom_theme.unwrap().sparkline, Color::Rgb(0, 255, 0));
+
+        assert!(toml::from_str::<Config>("[custom_theme]\ntitle = \"purple\"").is_err());
+    }
+
+    #[test]
+    fn test_theme_flag() {
+        let args = |a: &[&str]| Config::from_args(a.iter().map(|s| s.to_string()));
+        match args(&["--theme", "Ocean"]).unwrap() {
+            Command::Run(config) => assert_eq!(config.theme.as_deref(), Some("Ocean")),
+            _ => panic!("expected a run command"),
+        }
+
+        let err = args(&["--theme", "neon"]).err().unwrap().to_string();
+        assert!(err.contains("valid themes: gray, light"), "{}", err);
+        assert!(args(&["--theme", "custom"]).is_err());
+    }
+
+    #[test]
+    fn test_record_and_replay_flags() {
+        let args = |a: &[&str]| Config::from_args(a.iter().map(|s| s.to_string()));
+        match args(&["--replay", "/tmp/incident"]).unwrap() {
+            Command::Run(config) => assert_eq!(config.replay.as_deref(), Some("/tmp/incident")),
+            _ => panic!("expected a run command"),
+        }
+        assert!(args(&["--record", "/tmp/a", "--replay", "/tmp/b"]).is_err());
+        assert!(args(&["--replay", "/tmp/a", "--replay-speed", "0"]).is_err());
+        assert!(args(&["--replay", "/tmp/a", "--replay-speed", "NaN"]).is_err());
+
+        // The config file's value is checked too
+        let path = std::env::temp_dir().join(format!("monad-monitor-replay-speed-{}.toml", std::process::id()));
+        fs::write(&path, "replay_speed = -2.0\n").unwrap();
+        let err = args(&["--config", path.to_str().unwrap()]).err().unwrap().to_string();
+        assert!(err.contains("Invalid replay speed"), "{}", err);
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_network_flag() {
+        let args = |a: &[&str]| Config::from_args(a.iter().map(|s| s.to_string()));
+        match args(&["--network", "Testnet"]).unwrap() {
+            Command::Run(config) => {
+                assert_eq!(config.network, Network::Testnet);
+                assert_eq!(config.external_rpc_url(), "https://testnet-rpc.monad.xyz");
+            }
+            _ => panic!("expected a run command"),
+        }
+        match args(&["--network", "testnet", "--external-rpc", "http://10.0.0.5:8080"]).unwrap() {
+            Command::Run(config) => assert_eq!(config.external_rpc_url(), "http://10.0.0.5:8080"),
+            _ => panic!("expected a run command"),
+        }
+        assert!(args(&["--network", "devnet"]).is_err());
+
+        let config: Config = toml::from_str("network = \"testnet\"").unwrap();
+        assert_eq!(config.network, Network::Testnet);
+    }
+
+    #[test]
+    fn test_metrics_auth() {
+        let config: Config = toml::from_str("metrics_basic_user = \"prom\"\nmetrics_basic_password = \"secret\"").unwrap();
+        assert_eq!(
+            config.metrics_auth(),
+            Some(MetricsAuth::Basic { user: "prom".to_string(), password: Some("secret".to_string()) })
+        );
+
+        let config = Config { metrics_auth_bearer: Some("token".to_string()), ..config };
+        assert_eq!(config.metrics_auth(), Some(MetricsAuth::Bearer("token".to_string())));
+        let config = Config { metrics_auth: Some("Bearer raw".to_string()), ..config };
+        assert_eq!(config.metrics_auth(), Some(MetricsAuth::Header("Bearer raw".to_string())));
+        assert_eq!(Config::default().metrics_auth(), None);
+
+        let args = |a: &[&str]| Config::from_args(a.iter().map(|s| s.to_string()));
+        match args(&["--metrics-auth-bearer", "abc"]).unwrap() {
+            Command::Run(config) => assert_eq!(config.metrics_auth(), Some(MetricsAuth::Bearer("abc".to_string()))),
+            _ => panic!("expected a run command"),
+        }
+        assert!(args(&["--metrics-auth-bearer"]).is_err());
+    }
+
+    #[test]
+    fn test_nodes_from_toml() {
+        let config: Config = toml::from_str(
+            r#"
+            [[nodes]]
+            name = "validator-2"
+            rpc_url = "ws://10.0.0.2:8081"
+            metrics_url = "http://10.0.0.2:8889/metrics"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.nodes.len(), 1);
+        assert_eq!(config.nodes[0].name, "validator-2");
+
+        assert!(toml::from_str::<Config>("[[nodes]]\nname = \"validator-2\"").is_err());
+    }
+}
@@ -0,0 +1,118 @@
+use anyhow::{Context, Result};
+use std::fs::{self, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tokio::sync::mpsc;
+
+use crate::state::AppState;
+
+const CSV_HEADER: &str = "timestamp,block_height,tps,peers,latency_p99_ms,cpu_pct,mem_pct,disk_pct,gas_gwei";
+const FLUSH_INTERVAL: Duration = Duration::from_secs(5);
+const QUEUE_SIZE: usize = 64;
+
+/// Appends one row of key metrics per refresh to a CSV file
+pub struct CsvLogger {
+    lines: mpsc::Sender<String>,
+}
+
+impl CsvLogger {
+    /// Open `path` for appending (writing the header if the file is new or empty)
+    /// and start the writer thread. Write errors are passed to `on_error`.
+    pub fn spawn<F>(path: &str, on_error: F) -> Result<Self>
+    where
+        F: Fn(String) + Send + 'static,
+    {
+        Ok(Self {
+            lines: spawn_writer(path, Some(CSV_HEADER), on_error)?,
+        })
+    }
+
+    /// Queue a row for the current state; dropped if the writer has fallen behind
+    pub fn log(&self, state: &AppState) {
+        let _ = self.lines.try_send(format_csv_row(state));
+    }
+}
+
+/// Appends one JSON object per refresh (a `Snapshot`) to a file, for log pipelines
+pub struct JsonlLogger {
+    lines: mpsc::Sender<String>,
+}
+
+impl JsonlLogger {
+    pub fn spawn<F>(path: &str, on_error: F) -> Result<Self>
+    where
+        F: Fn(String) + Send + 'static,
+    {
+        Ok(Self {
+            lines: spawn_writer(path, None, on_error)?,
+        })
+    }
+
+    /// Queue a line for the current state; dropped if the writer has fallen behind
+    pub fn log(&self, state: &AppState) {
+        if let Ok(line) = serde_json::to_string(&state.snapshot()) {
+            let _ = self.lines.try_send(line);
+        }
+    }
+}
+
+/// Open `path` for appending and write queued lines on a blocking thread,
+/// so file I/O never stalls drawing
+pub fn spawn_writer<F>(path: &str, header: Option<&str>, on_error: F) -> Result<mpsc::Sender<String>>
+where
+    F: Fn(String) + Send + 'static,
+{
+    let is_new = fs::metadata(path).map(|m| m.len() == 0).unwrap_or(true);
+    let file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("Failed to open log file {}", path))?;
+
+    let mut writer = BufWriter::new(file);
+    if let Some(header) = header.filter(|_| is_new) {
+        writeln!(writer, "{}", header)?;
+        writer.flush()?;
+    }
+
+    let (lines, mut rx) = mpsc::channel::<String>(QUEUE_SIZE);
+    tokio::task::spawn_blocking(move || {
+        let mut last_flush = Instant::now();
+        while let Some(line) = rx.blocking_recv() {
+            if let Err(e) = writeln!(writer, "{}", line) {
+                on_error(e.to_string());
+                continue;
+            }
+            // Flush periodically so a crash loses at most a few seconds of lines
+            if last_flush.elapsed() >= FLUSH_INTERVAL {
+                if let Err(e) = writer.flush() {
+                    on_error(e.to_string());
+                }
+                last_flush = Instant::now();
+            }
+        }
+        let _ = writer.flush();
+    });
+
+    Ok(lines)
+}
+
+fn format_csv_row(state: &AppState) -> String {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+
+    format!(
+        "{},{},{:.2},{},{},{:.1},{:.1},{:.1},{:.2}",
+        timestamp,
+        state.block_height(),
+        state.tps,
+        state.metrics.peer_count,
+        state.metrics.latency_p99_ms,
+        state.system.cpu_usage_pct,
+        state.system.memory_used_pct,
+        state.system.disk_used_pct,
+        state.rpc_data.gas_price_gwei,
+    )
+}
@@ -0,0 +1,85 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Saved history older than this is from a different session and not worth showing
+const MAX_AGE_SECS: u64 = 3600;
+
+/// Sparkline history and peak TPS, saved so a restart doesn't start from an empty graph
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SavedHistory {
+    /// Unix seconds when the file was written
+    pub saved_at: u64,
+    pub tps_history: Vec<u64>,
+    /// Metrics timestamp (ms) of each `tps_history` entry
+    #[serde(default)]
+    pub tps_history_ms: Vec<u64>,
+    pub tps_peak: f64,
+    pub latency_history: Vec<u64>,
+}
+
+/// `$XDG_STATE_HOME/monad-monitor/history.json`, falling back to `~/.local/state`
+pub fn default_path() -> Option<PathBuf> {
+    let state_dir = std::env::var_os("XDG_STATE_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| Path::new(&home).join(".local/state")))?;
+    Some(state_dir.join("monad-monitor").join("history.json"))
+}
+
+/// Read history saved within the last hour; missing, unreadable or stale files yield None
+pub fn load(path: &Path) -> Option<SavedHistory> {
+    let contents = fs::read_to_string(path).ok()?;
+    let history: SavedHistory = serde_json::from_str(&contents).ok()?;
+    (now_secs().saturating_sub(history.saved_at) <= MAX_AGE_SECS).then_some(history)
+}
+
+/// Write `history` stamped with the current time, via a temp file so a crash mid-write
+/// can't leave a truncated file behind
+pub fn save(path: &Path, history: &SavedHistory) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    }
+    let history = SavedHistory { saved_at: now_secs(), ..history.clone() };
+    let tmp = path.with_extension("json.tmp");
+    fs::write(&tmp, serde_json::to_string(&history)?)
+        .with_context(|| format!("Failed to write {}", tmp.display()))?;
+    fs::rename(&tmp, path).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_save_and_load() {
+        let path = std::env::temp_dir()
+            .join(format!("monad-monitor-history-{}", std::process::id()))
+            .join("history.json");
+        let history = SavedHistory {
+            tps_history: vec![1, 2, 3],
+            tps_peak: 42.0,
+            ..Default::default()
+        };
+        save(&path, &history).unwrap();
+        let loaded = load(&path).unwrap();
+        assert_eq!(loaded.tps_history, vec![1, 2, 3]);
+        assert_eq!(loaded.tps_peak, 42.0);
+
+        // An hour-old file is ignored
+        let stale = SavedHistory { saved_at: now_secs() - MAX_AGE_SECS - 1, ..history };
+        fs::write(&path, serde_json::to_string(&stale).unwrap()).unwrap();
+        assert!(load(&path).is_none());
+
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+}
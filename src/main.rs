@@ -1,42 +1,71 @@
+mod alerts;
+mod clipboard;
+mod config;
+mod file_log;
+mod history;
 mod metrics;
+mod recording;
 mod rpc;
+mod server;
 mod state;
 mod system;
 mod ui;
+mod webhook;
 
-use std::io;
+use std::io::{self, Write};
+use std::sync::Arc;
 use std::time::Duration;
 
 use anyhow::Result;
 use crossterm::{
-    event::{DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind},
+    event::{DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind, MouseButton, MouseEventKind},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use futures::StreamExt;
 use ratatui::prelude::*;
-use tokio::sync::mpsc;
+use serde::{Deserialize, Serialize};
+use tokio::sync::{broadcast, mpsc, watch, Notify};
+use tokio::task::JoinHandle;
 use tokio::time::interval;
 
+use crate::clipboard::Clipboard;
+use crate::config::{Command, Config};
+use crate::file_log::{CsvLogger, JsonlLogger};
 use crate::metrics::{MetricsClient, PrometheusMetrics};
+use crate::recording::{spawn_replay, Recorder};
 use crate::rpc::{RpcClient, RpcData};
-use crate::state::AppState;
+use crate::server::{Format, StateServer};
+use crate::state::{AppState, DataSource, RefreshIntervals};
 use crate::system::{SystemClient, SystemData};
+use crate::webhook::WebhookClient;
 
 const METRICS_ENDPOINT: &str = "http://localhost:8889/metrics";
-const RPC_ENDPOINT: &str = "ws://localhost:8081";
-const NETWORK: &str = "mainnet";
-const METRICS_REFRESH_INTERVAL_MS: u64 = 1000;
-const SYSTEM_REFRESH_INTERVAL_MS: u64 = 5000;
+const HISTORY_SAVE_INTERVAL_SECS: u64 = 60;
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_millis(500);
 
+#[derive(Serialize, Deserialize)]
 enum DataUpdate {
     Metrics(Result<PrometheusMetrics, String>),
-    Rpc(RpcData),
-    System(Result<SystemData, String>),
+    Rpc(Result<RpcData, String>),
+    System(Result<Box<SystemData>, String>),
+    LogError(String),
 }
 
 #[tokio::main]
 async fn main() -> Result<()> {
+    let config = match Config::from_args(std::env::args().skip(1))? {
+        Command::Run(config) => *config,
+        Command::Help => {
+            println!("{}", Config::usage());
+            return Ok(());
+        }
+        Command::Version => {
+            println!("monad-monitor {}", env!("CARGO_PKG_VERSION"));
+            return Ok(());
+        }
+    };
+
     // Setup terminal
     enable_raw_mode()?;
     let mut stdout = io::stdout();
@@ -45,7 +74,7 @@ async fn main() -> Result<()> {
     let mut terminal = Terminal::new(backend)?;
 
     // Run app
-    let result = run_app(&mut terminal).await;
+    let result = run_app(&mut terminal, config).await;
 
     // Restore terminal
     disable_raw_mode()?;
@@ -63,54 +92,100 @@ async fn main() -> Result<()> {
     Ok(())
 }
 
-async fn run_app<B: Backend>(terminal: &mut Terminal<B>) -> Result<()> {
-    let mut state = AppState::new();
+async fn run_app<B: Backend>(terminal: &mut Terminal<B>, config: Config) -> Result<()> {
+    let mut state = AppState::new(config);
 
-    // Channel for receiving data updates from background tasks
-    let (tx, mut rx) = mpsc::channel::<DataUpdate>(100);
+    // A wrong triedb path silently yields all-zero disk stats, so flag it up front
+    if !std::path::Path::new(&state.config.triedb_path).exists() {
+        let notice = format!("triedb path {} does not exist, disk stats unavailable", state.config.triedb_path);
+        state.set_notice(notice);
+    }
 
-    // Spawn RPC subscription (real-time block updates)
-    let (rpc_tx, mut rpc_rx) = mpsc::channel::<RpcData>(100);
-    let rpc_client = RpcClient::new(RPC_ENDPOINT);
-    rpc_client.subscribe(rpc_tx);
+    // Pick up the sparklines where the last session left off
+    let history_path = state.config.persist_history.then(history::default_path).flatten();
+    if let Some(saved) = history_path.as_deref().and_then(history::load) {
+        state.restore_history(saved);
+    }
 
-    // Forward RPC updates to main channel
-    let tx_rpc = tx.clone();
-    tokio::spawn(async move {
-        while let Some(rpc_data) = rpc_rx.recv().await {
-            let _ = tx_rpc.send(DataUpdate::Rpc(rpc_data)).await;
+    // Channel for receiving data updates from background tasks, tagged with the node
+    // index (0 is the local node)
+    let (tx, mut rx) = mpsc::channel::<(usize, DataUpdate)>(100);
+
+    // File recording runs on its own threads; write errors come back as updates
+    let csv_logger = match state.config.log_csv.as_deref() {
+        Some(path) => {
+            let tx_csv = tx.clone();
+            Some(CsvLogger::spawn(path, move |e| {
+                let _ = tx_csv.blocking_send((0, DataUpdate::LogError(format!("csv: {}", e))));
+            })?)
         }
-    });
+        None => None,
+    };
+    let jsonl_logger = match state.config.log_jsonl.as_deref() {
+        Some(path) => {
+            let tx_jsonl = tx.clone();
+            Some(JsonlLogger::spawn(path, move |e| {
+                let _ = tx_jsonl.blocking_send((0, DataUpdate::LogError(format!("jsonl: {}", e))));
+            })?)
+        }
+        None => None,
+    };
+    let recorder = match state.config.record.as_deref() {
+        Some(dir) => {
+            let tx_record = tx.clone();
+            Some(Recorder::spawn(dir, move |e| {
+                let _ = tx_record.blocking_send((0, DataUpdate::LogError(format!("record: {}", e))));
+            })?)
+        }
+        None => None,
+    };
 
-    // Spawn background data fetcher for metrics (polling)
-    let tx_metrics = tx.clone();
-    tokio::spawn(async move {
-        let metrics_client = MetricsClient::new(METRICS_ENDPOINT);
-        let mut refresh_interval = interval(Duration::from_millis(METRICS_REFRESH_INTERVAL_MS));
+    // Background tasks; dropping `quit_tx` on quit tells each one to close its
+    // connections and stop, so none outlives the UI
+    let mut tasks: Vec<JoinHandle<()>> = Vec::new();
+    let (quit_tx, _) = broadcast::channel::<()>(1);
 
-        loop {
-            refresh_interval.tick().await;
-            let metrics_result = metrics_client.fetch().await;
-            let _ = tx_metrics.send(DataUpdate::Metrics(
-                metrics_result.map_err(|e| e.to_string())
-            )).await;
+    // Optional JSON / Prometheus endpoints, fed a fresh snapshot after every update
+    let mut servers = Vec::new();
+    if let Some(addr) = state.config.serve_json.as_deref() {
+        servers.push(StateServer::bind(addr, Format::Json).await?);
+    }
+    if let Some(addr) = state.config.prometheus_export.as_deref() {
+        servers.push(StateServer::bind(addr, Format::Prometheus).await?);
+    }
+    let snapshot_tx = (!servers.is_empty()).then(|| {
+        let (snapshot_tx, snapshot_rx) = watch::channel(state.snapshot());
+        for server in servers {
+            tasks.push(server.spawn(snapshot_rx.clone(), quit_tx.subscribe()));
         }
+        snapshot_tx
     });
 
-    // Spawn background data fetcher for system data (less frequent)
-    let tx_system = tx.clone();
-    tokio::spawn(async move {
-        let system_client = SystemClient::new(NETWORK);
-        let mut refresh_interval = interval(Duration::from_millis(SYSTEM_REFRESH_INTERVAL_MS));
+    let request_timeout = Duration::from_secs(state.config.request_timeout_secs.max(1));
+    let webhook = state
+        .config
+        .webhook_url
+        .as_deref()
+        .map(|url| WebhookClient::new(url, request_timeout));
 
-        loop {
-            refresh_interval.tick().await;
-            let system_result = system_client.fetch().await;
-            let _ = tx_system.send(DataUpdate::System(
-                system_result.map_err(|e| e.to_string())
-            )).await;
-        }
-    });
+    let remotes: Vec<AppState> =
+        state.config.nodes.iter().map(|node| AppState::remote(&state.config, node)).collect();
+    // Notified by `r` so every fetcher runs now instead of waiting for its next tick
+    let refresh = Arc::new(Notify::new());
+    // Current fetch intervals, changed with + / -
+    let (intervals_tx, intervals_rx) = watch::channel(state.refresh);
+    let fetch_control = FetchControl { refresh: refresh.clone(), intervals: intervals_rx, quit: quit_tx.subscribe() };
+    match state.config.replay.as_deref() {
+        // A recording stands in for every fetcher, remote nodes included
+        Some(dir) => tasks.push(spawn_replay(dir, state.config.replay_speed, tx.clone(), quit_tx.subscribe())?),
+        None => tasks.extend(spawn_fetchers(&state.config, request_timeout, &tx, &fetch_control)?),
+    }
+
+    let mut nodes = vec![state];
+    nodes.extend(remotes);
+    let mut focused = 0;
+
+    let mut clipboard = Clipboard::default();
 
     // Create async event stream for keyboard
     let mut event_stream = crossterm::event::EventStream::new();
@@ -118,37 +193,189 @@ async fn run_app<B: Backend>(terminal: &mut Terminal<B>) -> Result<()> {
     // UI refresh ticker for smooth animations (100ms = 10fps)
     let mut ui_ticker = interval(Duration::from_millis(100));
 
+    let mut history_ticker = interval(Duration::from_secs(HISTORY_SAVE_INTERVAL_SECS));
+    history_ticker.reset();
+
     loop {
         // Draw UI
-        terminal.draw(|frame| ui::draw(frame, &state))?;
+        terminal.draw(|frame| ui::draw(frame, &mut nodes, focused))?;
 
         // Wait for keyboard input, data update, or UI tick
         tokio::select! {
-            // Handle keyboard events (highest priority)
+            // Handle keyboard and mouse events (highest priority)
             maybe_event = event_stream.next() => {
+                let node_count = nodes.len();
+                let state = &mut nodes[focused];
                 if let Some(Ok(Event::Key(key))) = maybe_event {
-                    if key.kind == KeyEventKind::Press {
+                    if key.kind == KeyEventKind::Press && state.confirming_quit {
+                        // y or Esc confirms, anything else cancels
+                        if matches!(key.code, KeyCode::Char('y') | KeyCode::Char('Y') | KeyCode::Esc) {
+                            break;
+                        }
+                        state.confirming_quit = false;
+                    } else if key.kind == KeyEventKind::Press {
                         match key.code {
-                            KeyCode::Char('q') | KeyCode::Char('Q') | KeyCode::Esc => {
-                                return Ok(());
+                            // Esc closes open popups before quitting
+                            KeyCode::Esc if state.show_help => {
+                                state.show_help = false;
+                            }
+                            KeyCode::Esc if state.show_error_log => {
+                                state.show_error_log = false;
+                            }
+                            KeyCode::Esc if state.show_block_detail => {
+                                state.show_block_detail = false;
+                            }
+                            // With confirm_quit, the first q only shows the prompt
+                            KeyCode::Char('q') | KeyCode::Char('Q') if state.request_quit() => {
+                                break;
+                            }
+                            KeyCode::Esc => {
+                                break;
                             }
                             KeyCode::Char('t') | KeyCode::Char('T') => {
                                 state.toggle_theme();
                             }
+                            // Move the detailed view to the next node, keeping theme and layout
+                            KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Tab | KeyCode::BackTab
+                                if node_count > 1 =>
+                            {
+                                let next = if key.code == KeyCode::BackTab {
+                                    (focused + node_count - 1) % node_count
+                                } else {
+                                    (focused + 1) % node_count
+                                };
+                                if let Ok([current, next_node]) = nodes.get_disjoint_mut([focused, next]) {
+                                    next_node.take_view_from(current);
+                                    focused = next;
+                                }
+                            }
+                            // 1-9 pick a theme directly, 0 the tenth (custom)
+                            KeyCode::Char(c @ '0'..='9') => {
+                                let digit = c.to_digit(10).unwrap_or(0) as usize;
+                                state.select_theme((digit + 9) % 10);
+                            }
+                            KeyCode::Char('?') => {
+                                state.toggle_help();
+                            }
+                            KeyCode::Char('g') | KeyCode::Char('G') => {
+                                state.cycle_graph();
+                            }
+                            KeyCode::Down | KeyCode::Char('j') => {
+                                state.select_next_block();
+                            }
+                            KeyCode::Up | KeyCode::Char('k') => {
+                                state.select_prev_block();
+                            }
+                            KeyCode::Enter => {
+                                state.toggle_block_detail();
+                            }
+                            // Copy the selected block's hash; without a clipboard, show it instead
+                            KeyCode::Char('y') | KeyCode::Char('Y') => {
+                                let notice = match state.selected_hash().map(str::to_string) {
+                                    Some(hash) => match clipboard.copy(&hash) {
+                                        Ok(()) => format!("copied {}", hash),
+                                        Err(e) => format!("{}, hash: {}", e, hash),
+                                    },
+                                    None => "select a block with ↑↓ to copy its hash".to_string(),
+                                };
+                                state.set_notice(notice);
+                            }
+                            KeyCode::Char('w') | KeyCode::Char('W') => {
+                                state.toggle_absolute_time();
+                            }
+                            KeyCode::Char('r') | KeyCode::Char('R') => {
+                                refresh.notify_waiters();
+                            }
+                            // Halve or double the fetch intervals, for every node
+                            KeyCode::Char(c @ ('+' | '=' | '-')) => {
+                                let intervals = state.refresh.scaled(c != '-');
+                                for node in nodes.iter_mut() {
+                                    node.refresh = intervals;
+                                }
+                                intervals_tx.send_replace(intervals);
+                            }
+                            KeyCode::Char('s') | KeyCode::Char('S') if node_count > 1 => {
+                                state.toggle_split_view();
+                            }
+                            KeyCode::Char('p') | KeyCode::Char('P') => {
+                                state.toggle_peak_window();
+                            }
+                            KeyCode::Char('c') | KeyCode::Char('C') => {
+                                state.toggle_compact();
+                            }
+                            KeyCode::Char('e') | KeyCode::Char('E') => {
+                                state.toggle_error_log();
+                            }
+                            KeyCode::Char('a') | KeyCode::Char('A') => {
+                                state.acknowledge_alerts();
+                            }
                             _ => {}
                         }
                     }
+                } else if let Some(Ok(Event::Mouse(mouse))) = maybe_event {
+                    if mouse.kind == MouseEventKind::Down(MouseButton::Left) {
+                        state.click(mouse.column, mouse.row);
+                    }
                 }
             }
 
             // Handle data updates from background tasks
-            Some(update) = rx.recv() => {
+            Some((index, update)) = rx.recv() => {
+                // Local write errors aren't node data, so they stay out of recordings
+                if let Some(recorder) = recorder.as_ref().filter(|_| !matches!(update, DataUpdate::LogError(_))) {
+                    recorder.record(index, &update);
+                }
+                let Some(state) = nodes.get_mut(index) else { continue };
                 match update {
-                    DataUpdate::Metrics(Ok(metrics)) => state.update_metrics(metrics),
-                    DataUpdate::Metrics(Err(e)) => state.set_error(format!("metrics: {}", e)),
-                    DataUpdate::Rpc(rpc_data) => state.update_rpc(rpc_data),
-                    DataUpdate::System(Ok(system)) => state.update_system(system),
-                    DataUpdate::System(Err(e)) => state.set_error(format!("system: {}", e)),
+                    DataUpdate::Metrics(Ok(metrics)) => {
+                        state.update_metrics(metrics);
+                        // Recording and export follow the local node
+                        if index == 0 {
+                            if let Some(logger) = &csv_logger {
+                                logger.log(state);
+                            }
+                            if let Some(logger) = &jsonl_logger {
+                                logger.log(state);
+                            }
+                        }
+                    }
+                    DataUpdate::Metrics(Err(e)) => state.source_failed(DataSource::Metrics, format!("metrics: {}", e)),
+                    DataUpdate::Rpc(Ok(rpc_data)) => state.update_rpc(rpc_data),
+                    DataUpdate::Rpc(Err(e)) => state.source_failed(DataSource::Rpc, format!("rpc: {}", e)),
+                    DataUpdate::System(Ok(system)) => {
+                        let external_block = system.external_block;
+                        state.update_system(*system);
+                        for remote in nodes.iter_mut().skip(1) {
+                            remote.update_reference_block(external_block);
+                        }
+                    }
+                    DataUpdate::System(Err(e)) => state.source_failed(DataSource::System, format!("system: {}", e)),
+                    DataUpdate::LogError(e) => state.set_error(e),
+                }
+                // Ring when an alert fires, then periodically until acknowledged
+                let state = &mut nodes[index];
+                let events = state.update_alerts();
+                if state.bell_due(&events) {
+                    let mut stdout = io::stdout();
+                    let _ = stdout.write_all(b"\x07");
+                    let _ = stdout.flush();
+                }
+                if let Some(webhook) = &webhook {
+                    for event in &events {
+                        webhook.notify(&state.system.node_id, event);
+                    }
+                }
+                if let (Some(snapshot_tx), 0) = (&snapshot_tx, index) {
+                    snapshot_tx.send_replace(state.snapshot());
+                }
+            }
+
+            // Periodic save, so a crash or kill loses at most a minute of history
+            _ = history_ticker.tick(), if history_path.is_some() => {
+                if let Some(path) = &history_path {
+                    if let Err(e) = history::save(path, &nodes[0].saved_history()) {
+                        nodes[0].set_error(format!("history: {:#}", e));
+                    }
                 }
             }
 
@@ -158,4 +385,160 @@ async fn run_app<B: Backend>(terminal: &mut Terminal<B>) -> Result<()> {
             }
         }
     }
+
+    shutdown(quit_tx, tasks).await;
+    if let Some(path) = &history_path {
+        history::save(path, &nodes[0].saved_history())?;
+    }
+    Ok(())
+}
+
+/// Lets the UI steer the fetchers: `refresh` makes them run now, `intervals` sets
+/// how often they run, and `quit` closes when they should stop
+struct FetchControl {
+    refresh: Arc<Notify>,
+    intervals: watch::Receiver<RefreshIntervals>,
+    quit: broadcast::Receiver<()>,
+}
+
+impl Clone for FetchControl {
+    fn clone(&self) -> Self {
+        Self {
+            refresh: self.refresh.clone(),
+            intervals: self.intervals.clone(),
+            // Nothing is ever sent, so a receiver that only sees the close is enough
+            quit: self.quit.resubscribe(),
+        }
+    }
+}
+
+/// Tell the background tasks to stop by dropping `quit_tx` and give them a moment to
+/// close their connections before the terminal is restored; any still running after
+/// that are aborted
+async fn shutdown(quit_tx: broadcast::Sender<()>, tasks: Vec<JoinHandle<()>>) {
+    drop(quit_tx);
+    let aborts: Vec<_> = tasks.iter().map(JoinHandle::abort_handle).collect();
+    let _ = tokio::time::timeout(SHUTDOWN_TIMEOUT, futures::future::join_all(tasks)).await;
+    for abort in aborts {
+        abort.abort();
+    }
+}
+
+/// Start the live fetchers: RPC and metrics for the local node and each configured
+/// remote one, plus the local system stats. Returns the task handles for `shutdown`.
+fn spawn_fetchers(
+    config: &Config,
+    request_timeout: Duration,
+    tx: &mpsc::Sender<(usize, DataUpdate)>,
+    control: &FetchControl,
+) -> Result<Vec<JoinHandle<()>>> {
+    // RPC and metrics fetchers for the local node, then for each configured remote one
+    let mut tasks = spawn_node_fetchers(0, &config.rpc_url, METRICS_ENDPOINT, config, request_timeout, tx, control)?;
+    for (i, node) in config.nodes.iter().enumerate() {
+        tasks.extend(spawn_node_fetchers(
+            i + 1,
+            &node.rpc_url,
+            &node.metrics_url,
+            config,
+            request_timeout,
+            tx,
+            control,
+        )?);
+    }
+
+    // Spawn background data fetcher for system data (less frequent)
+    let tx_system = tx.clone();
+    let services = config.services.clone();
+    let triedb_path = config.triedb_path.clone();
+    let external_rpc = config.external_rpc_url();
+    let rpc_auth = config.rpc_auth.clone();
+    let mut control = control.clone();
+    tasks.push(tokio::spawn(async move {
+        let mut system_client =
+            SystemClient::new(services, &triedb_path, external_rpc, rpc_auth, request_timeout);
+        let mut refresh_interval = interval(control.intervals.borrow_and_update().system);
+
+        loop {
+            tokio::select! {
+                _ = refresh_interval.tick() => {}
+                _ = control.refresh.notified() => {}
+                // Fetch once now and then keep to the new interval
+                Ok(()) = control.intervals.changed() => {
+                    refresh_interval = interval(control.intervals.borrow_and_update().system);
+                    refresh_interval.tick().await;
+                }
+                _ = control.quit.recv() => break,
+            }
+            let system_result = tokio::select! {
+                result = system_client.fetch() => result,
+                _ = control.quit.recv() => break,
+            };
+            let _ = tx_system.send((0, DataUpdate::System(
+                system_result.map(Box::new).map_err(|e| e.to_string())
+            ))).await;
+        }
+    }));
+
+    Ok(tasks)
+}
+
+/// Spawn the RPC subscription (real-time block updates, or polling for http:// endpoints)
+/// and the metrics poller for one node, tagging their updates with `index`; credentials
+/// come from `config`. Returns the task handles for `shutdown`.
+fn spawn_node_fetchers(
+    index: usize,
+    rpc_url: &str,
+    metrics_url: &str,
+    config: &Config,
+    request_timeout: Duration,
+    tx: &mpsc::Sender<(usize, DataUpdate)>,
+    control: &FetchControl,
+) -> Result<Vec<JoinHandle<()>>> {
+    let (rpc_tx, mut rpc_rx) = mpsc::channel::<Result<RpcData, String>>(100);
+    let rpc_client = RpcClient::new(rpc_url, config.rpc_auth.clone(), request_timeout)?;
+    let mut tasks =
+        vec![rpc_client.subscribe(rpc_tx, control.refresh.clone(), control.intervals.clone(), control.quit.resubscribe())];
+
+    // Forward RPC updates to main channel
+    let tx_rpc = tx.clone();
+    tasks.push(tokio::spawn(async move {
+        while let Some(rpc_data) = rpc_rx.recv().await {
+            let _ = tx_rpc.send((index, DataUpdate::Rpc(rpc_data))).await;
+        }
+    }));
+
+    // Spawn background data fetcher for metrics (polling)
+    let tx_metrics = tx.clone();
+    let metrics_client = MetricsClient::new(
+        metrics_url,
+        config.metrics_auth(),
+        config.metric_names.clone(),
+        request_timeout,
+    );
+    let mut control = control.clone();
+    tasks.push(tokio::spawn(async move {
+        let mut refresh_interval = interval(control.intervals.borrow_and_update().metrics);
+
+        loop {
+            tokio::select! {
+                _ = refresh_interval.tick() => {}
+                _ = control.refresh.notified() => {}
+                // Fetch once now and then keep to the new interval
+                Ok(()) = control.intervals.changed() => {
+                    refresh_interval = interval(control.intervals.borrow_and_update().metrics);
+                    refresh_interval.tick().await;
+                }
+                _ = control.quit.recv() => break,
+            }
+            let metrics_result = tokio::select! {
+                result = metrics_client.fetch() => result,
+                _ = control.quit.recv() => break,
+            };
+            let _ = tx_metrics.send((index, DataUpdate::Metrics(
+                metrics_result.map_err(|e| e.to_string())
+            ))).await;
+        }
+    }));
+
+    Ok(tasks)
 }
@@ -1,8 +1,12 @@
+use crate::config::MetricNames;
 use anyhow::{Context, Result};
-use reqwest::Client;
+use reqwest::{header::AUTHORIZATION, Client};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::Duration;
 
 /// Metrics fetched from Prometheus endpoint
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct PrometheusMetrics {
     pub block_num: u64,
     pub tx_commits: u64,
@@ -13,8 +17,14 @@ pub struct PrometheusMetrics {
     // New metrics
     pub uptime_us: u64,
     pub latency_p99_ms: u64,
+    // Percentiles from the latency summary or histogram (0 when the exporter only has the p99 gauge)
+    pub latency_p50_ms: u64,
+    pub latency_p95_ms: u64,
     pub pending_txs: u64,
     pub upstream_validators: u64,
+    // Consensus progress (0 when the exporter doesn't report it)
+    pub consensus_round: u64,
+    pub consensus_view: u64,
 }
 
 impl PrometheusMetrics {
@@ -31,23 +41,41 @@ impl PrometheusMetrics {
     }
 }
 
+/// Credentials sent to a metrics endpoint behind an auth proxy
+#[derive(Debug, Clone, PartialEq)]
+pub enum MetricsAuth {
+    /// Full Authorization header value, e.g. "Bearer <token>" or "Basic <base64>"
+    Header(String),
+    Bearer(String),
+    Basic { user: String, password: Option<String> },
+}
+
 pub struct MetricsClient {
     client: Client,
     endpoint: String,
+    auth: Option<MetricsAuth>,
+    names: MetricNames,
 }
 
 impl MetricsClient {
-    pub fn new(endpoint: &str) -> Self {
+    pub fn new(endpoint: &str, auth: Option<MetricsAuth>, names: MetricNames, timeout: Duration) -> Self {
         Self {
-            client: Client::new(),
+            client: Client::builder().timeout(timeout).build().unwrap_or_default(),
             endpoint: endpoint.to_string(),
+            auth,
+            names,
         }
     }
 
     pub async fn fetch(&self) -> Result<PrometheusMetrics> {
-        let body = self
-            .client
-            .get(&self.endpoint)
+        let mut request = self.client.get(&self.endpoint);
+        request = match &self.auth {
+            Some(MetricsAuth::Header(value)) => request.header(AUTHORIZATION, value),
+            Some(MetricsAuth::Bearer(token)) => request.bearer_auth(token),
+            Some(MetricsAuth::Basic { user, password }) => request.basic_auth(user, password.as_ref()),
+            None => request,
+        };
+        let body = request
             .send()
             .await
             .context("Failed to fetch metrics")?
@@ -55,12 +83,65 @@ impl MetricsClient {
             .await
             .context("Failed to read metrics body")?;
 
-        parse_metrics(&body)
+        parse_metrics(&body, &self.names)
     }
 }
 
-fn parse_metrics(body: &str) -> Result<PrometheusMetrics> {
-    let mut metrics = PrometheusMetrics::default();
+/// Cumulative buckets of a Prometheus histogram
+#[derive(Debug, Default)]
+struct Histogram {
+    /// (upper bound `le`, cumulative count), combined across other labels
+    buckets: Vec<(f64, f64)>,
+    count: f64,
+}
+
+impl Histogram {
+    /// Estimate the `q` quantile (0.0..=1.0) by linear interpolation inside the bucket
+    /// it falls in, like PromQL's `histogram_quantile`
+    fn quantile(&self, q: f64) -> Option<f64> {
+        let mut buckets = self.buckets.clone();
+        buckets.sort_by(|a, b| a.0.total_cmp(&b.0));
+        // A summary's _count comes without buckets, which leaves nothing to estimate from
+        let last = buckets.last()?.1;
+        let total = if self.count > 0.0 { self.count } else { last };
+        if total <= 0.0 {
+            return None;
+        }
+
+        let rank = q * total;
+        let (mut lower, mut below) = (0.0, 0.0);
+        for &(le, cumulative) in &buckets {
+            if cumulative >= rank {
+                // The +Inf bucket has no upper bound to interpolate towards
+                if le.is_infinite() {
+                    return Some(lower);
+                }
+                let in_bucket = cumulative - below;
+                let fraction = if in_bucket > 0.0 { (rank - below) / in_bucket } else { 0.0 };
+                return Some(lower + (le - lower) * fraction);
+            }
+            lower = le;
+            below = cumulative;
+        }
+        Some(lower)
+    }
+}
+
+fn parse_metrics(body: &str, names: &MetricNames) -> Result<PrometheusMetrics> {
+    // Counts add up across series split by label; everything else (heights, rounds,
+    // latencies) takes the largest series
+    let summed = [&names.tx_commits, &names.peer_count, &names.pending_txs, &names.upstream_validators];
+    // Latency may come as a summary (`{quantile="0.99"}` series) or a histogram
+    // (`_bucket{le="..."}` series), whichever the node's exporter uses
+    let bucket_name = format!("{}_bucket", names.latency);
+    let count_name = format!("{}_count", names.latency);
+    let mut latency = Histogram::default();
+    // (quantile, value) from a summary, which needs no estimating
+    let mut latency_quantiles: Vec<(f64, f64)> = Vec::new();
+
+    // Value and latest timestamp per metric name, combining series that differ only in
+    // labels (per peer, per shard, ...) instead of letting the last line win
+    let mut series: HashMap<&str, (f64, u64)> = HashMap::new();
 
     for line in body.lines() {
         // Skip comments and empty lines
@@ -70,59 +151,104 @@ fn parse_metrics(body: &str) -> Result<PrometheusMetrics> {
 
         // Parse metric lines: metric_name{labels} value timestamp
         // or: metric_name value timestamp
-        if let Some((name, value, timestamp)) = parse_metric_line(line) {
-            match name {
-                "monad_execution_ledger_block_num" => {
-                    metrics.block_num = value as u64;
+        if let Some((name, labels, value, timestamp)) = parse_metric_line(line) {
+            if name == bucket_name {
+                let Some(le) = label_value(labels, "le").and_then(|le| le.parse::<f64>().ok()) else {
+                    continue;
+                };
+                match latency.buckets.iter_mut().find(|(bound, _)| *bound == le) {
+                    Some((_, cumulative)) => *cumulative += value,
+                    None => latency.buckets.push((le, value)),
                 }
-                "monad_execution_ledger_num_tx_commits" => {
-                    metrics.tx_commits = value as u64;
-                    metrics.tx_commits_timestamp_ms = timestamp;
-                }
-                "monad_peer_disc_num_peers" => {
-                    metrics.peer_count = value as u64;
-                }
-                "monad_statesync_progress_estimate" => {
-                    metrics.statesync_progress = value as u64;
-                }
-                "monad_statesync_last_target" => {
-                    metrics.statesync_target = value as u64;
-                }
-                "monad_total_uptime_us" => {
-                    metrics.uptime_us = value as u64;
-                }
-                "monad_bft_raptorcast_udp_secondary_broadcast_latency_p99_ms" => {
-                    metrics.latency_p99_ms = value as u64;
-                }
-                "monad_bft_txpool_pool_tracked_txs" => {
-                    metrics.pending_txs = value as u64;
-                }
-                "monad_peer_disc_num_upstream_validators" => {
-                    metrics.upstream_validators = value as u64;
+                continue;
+            }
+            if name == names.latency {
+                if let Some(q) = label_value(labels, "quantile").and_then(|q| q.parse::<f64>().ok()) {
+                    match latency_quantiles.iter_mut().find(|(quantile, _)| *quantile == q) {
+                        Some((_, worst)) => *worst = worst.max(value),
+                        None => latency_quantiles.push((q, value)),
+                    }
                 }
-                _ => {}
+                continue;
+            }
+            if name == count_name {
+                latency.count += value;
+                continue;
             }
+            series
+                .entry(name)
+                .and_modify(|(total, latest)| {
+                    *total = if summed.iter().any(|summed| *summed == name) { *total + value } else { total.max(value) };
+                    *latest = (*latest).max(timestamp);
+                })
+                .or_insert((value, timestamp));
+        }
+    }
+
+    let value = |name: &str| series.get(name).map_or(0, |&(value, _)| value as u64);
+    let mut metrics = PrometheusMetrics {
+        block_num: value(&names.block_num),
+        tx_commits: value(&names.tx_commits),
+        tx_commits_timestamp_ms: series.get(names.tx_commits.as_str()).map_or(0, |&(_, timestamp)| timestamp),
+        peer_count: value(&names.peer_count),
+        statesync_progress: value(&names.statesync_progress),
+        statesync_target: value(&names.statesync_target),
+        uptime_us: value(&names.uptime),
+        latency_p99_ms: value(&names.latency_p99),
+        pending_txs: value(&names.pending_txs),
+        upstream_validators: value(&names.upstream_validators),
+        consensus_round: value(&names.consensus_round),
+        consensus_view: value(&names.consensus_view),
+        ..Default::default()
+    };
+
+    // Summary quantiles are exact; histogram buckets only give an estimate
+    let percentile = |q: f64| {
+        latency_quantiles
+            .iter()
+            .find(|(quantile, _)| *quantile == q)
+            .map(|&(_, value)| value)
+            .filter(|value| !value.is_nan())
+            .or_else(|| latency.quantile(q))
+            .map(|ms| ms.round() as u64)
+    };
+    metrics.latency_p50_ms = percentile(0.50).unwrap_or(0);
+    metrics.latency_p95_ms = percentile(0.95).unwrap_or(0);
+    // Prefer the exporter's own p99 gauge
+    if !series.contains_key(names.latency_p99.as_str()) {
+        if let Some(p99) = percentile(0.99) {
+            metrics.latency_p99_ms = p99;
         }
     }
 
     Ok(metrics)
 }
 
-fn parse_metric_line(line: &str) -> Option<(&str, f64, u64)> {
+/// Value of `key` in a raw label set such as `le="0.5",job="node"`
+fn label_value<'a>(labels: &'a str, key: &str) -> Option<&'a str> {
+    labels.split(',').find_map(|pair| {
+        let (name, value) = pair.split_once('=')?;
+        (name.trim() == key).then(|| value.trim().trim_matches('"'))
+    })
+}
+
+/// Split a sample line into name, raw label set (without braces, "" if none), value
+/// and optional timestamp
+fn parse_metric_line(line: &str) -> Option<(&str, &str, f64, u64)> {
     // Handle lines with labels: metric_name{label="value"} 123.45 1234567890
     // Handle lines without labels: metric_name 123.45 1234567890
 
-    let (name, rest) = if let Some(brace_pos) = line.find('{') {
+    let (name, labels, rest) = if let Some(brace_pos) = line.find('{') {
         let name = &line[..brace_pos];
         // Find closing brace and skip to value
-        let after_brace = line.find('}')?;
-        (name, line[after_brace + 1..].trim())
+        let after_brace = line.rfind('}')?;
+        (name, &line[brace_pos + 1..after_brace], line[after_brace + 1..].trim())
     } else {
         // No labels, split on first whitespace
         let mut parts = line.splitn(2, char::is_whitespace);
         let name = parts.next()?;
         let rest = parts.next()?.trim();
-        (name, rest)
+        (name, "", rest)
     };
 
     // Parse value and optional timestamp
@@ -130,7 +256,7 @@ fn parse_metric_line(line: &str) -> Option<(&str, f64, u64)> {
     let value: f64 = parts.next()?.parse().ok()?;
     let timestamp: u64 = parts.next().and_then(|s| s.parse().ok()).unwrap_or(0);
 
-    Some((name, value, timestamp))
+    Some((name, labels, value, timestamp))
 }
 
 #[cfg(test)]
@@ -140,9 +266,93 @@ mod tests {
     #[test]
     fn test_parse_metric_line() {
         let line = r#"monad_execution_ledger_block_num{job="test"} 4.1929095e+07 1765694534456"#;
-        let (name, value, ts) = parse_metric_line(line).unwrap();
+        let (name, labels, value, ts) = parse_metric_line(line).unwrap();
         assert_eq!(name, "monad_execution_ledger_block_num");
+        assert_eq!(labels, r#"job="test""#);
         assert_eq!(value as u64, 41929095);
         assert_eq!(ts, 1765694534456);
     }
+
+    #[test]
+    fn test_parse_consensus_metrics() {
+        let metrics = parse_metrics("monad_bft_consensus_round 1200\nmonad_bft_consensus_view 1195\n", &MetricNames::default()).unwrap();
+        assert_eq!(metrics.consensus_round, 1200);
+        assert_eq!(metrics.consensus_view, 1195);
+
+        // Older exporters without them leave zeros
+        let metrics = parse_metrics("monad_peer_disc_num_peers 40\n", &MetricNames::default()).unwrap();
+        assert_eq!(metrics.consensus_round, 0);
+    }
+
+    #[test]
+    fn test_parse_labeled_series() {
+        let body = r#"monad_peer_disc_num_peers{kind="full"} 30 1000
+monad_peer_disc_num_peers{kind="validator"} 12 1001
+monad_execution_ledger_block_num{shard="a"} 500
+monad_execution_ledger_block_num{shard="b"} 498
+"#;
+        let metrics = parse_metrics(body, &MetricNames::default()).unwrap();
+        assert_eq!(metrics.peer_count, 42);
+        assert_eq!(metrics.block_num, 500);
+    }
+
+    #[test]
+    fn test_renamed_metrics() {
+        let names = MetricNames { peer_count: "monad_peers_connected".to_string(), ..Default::default() };
+        let metrics = parse_metrics("monad_peers_connected 17\nmonad_peer_disc_num_peers 40\n", &names).unwrap();
+        assert_eq!(metrics.peer_count, 17);
+
+        let metrics = parse_metrics("monad_total_uptime_us 3.6e+09\n", &MetricNames::default()).unwrap();
+        assert_eq!(metrics.uptime_us, 3_600_000_000);
+        let names = MetricNames { uptime: "monad_uptime_us".to_string(), ..Default::default() };
+        let metrics = parse_metrics("monad_uptime_us 5000\n", &names).unwrap();
+        assert_eq!(metrics.uptime_us, 5_000);
+    }
+
+    #[test]
+    fn test_latency_histogram() {
+        let body = r#"monad_bft_raptorcast_udp_secondary_broadcast_latency_ms_bucket{le="10"} 50
+monad_bft_raptorcast_udp_secondary_broadcast_latency_ms_bucket{le="100"} 90
+monad_bft_raptorcast_udp_secondary_broadcast_latency_ms_bucket{le="1000"} 100
+monad_bft_raptorcast_udp_secondary_broadcast_latency_ms_bucket{le="+Inf"} 100
+monad_bft_raptorcast_udp_secondary_broadcast_latency_ms_sum 4200
+monad_bft_raptorcast_udp_secondary_broadcast_latency_ms_count 100
+"#;
+        let metrics = parse_metrics(body, &MetricNames::default()).unwrap();
+        assert_eq!(metrics.latency_p50_ms, 10);
+        // 95th of 100 lies halfway through the 100..1000 bucket
+        assert_eq!(metrics.latency_p95_ms, 550);
+        assert_eq!(metrics.latency_p99_ms, 910);
+
+        // The exporter's p99 gauge wins over the estimate
+        let body = format!("{body}monad_bft_raptorcast_udp_secondary_broadcast_latency_p99_ms 42\n");
+        let metrics = parse_metrics(&body, &MetricNames::default()).unwrap();
+        assert_eq!(metrics.latency_p99_ms, 42);
+        assert_eq!(metrics.latency_p95_ms, 550);
+    }
+
+    #[test]
+    fn test_latency_summary() {
+        let body = r#"monad_bft_raptorcast_udp_secondary_broadcast_latency_ms{quantile="0.5"} 12.4
+monad_bft_raptorcast_udp_secondary_broadcast_latency_ms{quantile="0.95"} 80
+monad_bft_raptorcast_udp_secondary_broadcast_latency_ms{quantile="0.99"} 240.6
+monad_bft_raptorcast_udp_secondary_broadcast_latency_ms_sum 4200
+monad_bft_raptorcast_udp_secondary_broadcast_latency_ms_count 100
+"#;
+        let metrics = parse_metrics(body, &MetricNames::default()).unwrap();
+        assert_eq!(metrics.latency_p50_ms, 12);
+        assert_eq!(metrics.latency_p95_ms, 80);
+        assert_eq!(metrics.latency_p99_ms, 241);
+
+        // _count alone doesn't make up a histogram
+        let metrics = parse_metrics("monad_bft_raptorcast_udp_secondary_broadcast_latency_ms_count 100\n", &MetricNames::default()).unwrap();
+        assert_eq!(metrics.latency_p99_ms, 0);
+    }
+
+    #[test]
+    fn test_label_value() {
+        assert_eq!(label_value(r#"le="0.5",job="node""#, "le"), Some("0.5"));
+        assert_eq!(label_value(r#"job="node", quantile="0.99""#, "quantile"), Some("0.99"));
+        assert_eq!(label_value(r#"job="node""#, "le"), None);
+    }
 }
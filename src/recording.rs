@@ -0,0 +1,137 @@
+use anyhow::{Context, Result};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::sync::{broadcast, mpsc};
+use tokio::task::JoinHandle;
+use tokio::time::{sleep_until, Instant};
+
+use crate::file_log::spawn_writer;
+
+const RECORDING_FILE: &str = "updates.jsonl";
+
+/// One line of a recording: an update for `node`, stamped with when it arrived
+#[derive(Debug, Serialize, Deserialize)]
+struct Entry<T> {
+    /// Unix milliseconds
+    at_ms: u64,
+    node: usize,
+    update: T,
+}
+
+/// Saves every update as it arrives, one JSON line each, for `spawn_replay`
+pub struct Recorder {
+    lines: mpsc::Sender<String>,
+}
+
+impl Recorder {
+    /// Start a fresh recording in `dir` (replacing any earlier one there) and its
+    /// writer thread. Write errors are passed to `on_error`.
+    pub fn spawn<F>(dir: &str, on_error: F) -> Result<Self>
+    where
+        F: Fn(String) + Send + 'static,
+    {
+        fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir))?;
+        let path = recording_path(dir);
+        match fs::remove_file(&path) {
+            Err(e) if e.kind() != ErrorKind::NotFound => {
+                return Err(e).with_context(|| format!("Failed to replace {}", path.display()));
+            }
+            _ => {}
+        }
+        Ok(Self {
+            lines: spawn_writer(&path.to_string_lossy(), None, on_error)?,
+        })
+    }
+
+    /// Queue an update; dropped if the writer has fallen behind
+    pub fn record<T: Serialize>(&self, node: usize, update: &T) {
+        let entry = Entry { at_ms: now_ms(), node, update };
+        if let Ok(line) = serde_json::to_string(&entry) {
+            let _ = self.lines.try_send(line);
+        }
+    }
+}
+
+/// Read the recording in `dir` and send its updates to `tx` with the spacing they
+/// were recorded with, divided by `speed`, until done or `quit`'s sender is dropped
+pub fn spawn_replay<T>(
+    dir: &str,
+    speed: f64,
+    tx: mpsc::Sender<(usize, T)>,
+    mut quit: broadcast::Receiver<()>,
+) -> Result<JoinHandle<()>>
+where
+    T: DeserializeOwned + Send + 'static,
+{
+    let entries: Vec<Entry<T>> = load(&recording_path(dir))?;
+    Ok(tokio::spawn(async move {
+        let started = Instant::now();
+        let first_ms = entries.first().map_or(0, |entry| entry.at_ms);
+        for entry in entries {
+            let offset = Duration::from_millis(entry.at_ms.saturating_sub(first_ms));
+            tokio::select! {
+                _ = sleep_until(started + offset.div_f64(speed)) => {}
+                _ = quit.recv() => break,
+            }
+            if tx.send((entry.node, entry.update)).await.is_err() {
+                break;
+            }
+        }
+    }))
+}
+
+fn recording_path(dir: &str) -> PathBuf {
+    Path::new(dir).join(RECORDING_FILE)
+}
+
+fn load<T: DeserializeOwned>(path: &Path) -> Result<Vec<Entry<T>>> {
+    let contents =
+        fs::read_to_string(path).with_context(|| format!("Failed to read recording {}", path.display()))?;
+    contents
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.is_empty())
+        .map(|(i, line)| {
+            serde_json::from_str(line).with_context(|| format!("Invalid entry on line {} of {}", i + 1, path.display()))
+        })
+        .collect()
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load() {
+        let dir = std::env::temp_dir().join(format!("monad-monitor-recording-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(RECORDING_FILE);
+        let lines: Vec<String> = [(1_000, 0, "first"), (1_500, 2, "second")]
+            .iter()
+            .map(|&(at_ms, node, update)| serde_json::to_string(&Entry { at_ms, node, update }).unwrap())
+            .collect();
+        fs::write(&path, lines.join("\n") + "\n").unwrap();
+
+        let entries: Vec<Entry<String>> = load(&path).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!((entries[1].at_ms, entries[1].node, entries[1].update.as_str()), (1_500, 2, "second"));
+
+        // A truncated line names where the recording is broken
+        fs::write(&path, "{\"at_ms\":1").unwrap();
+        let err = load::<String>(&path).unwrap_err();
+        assert!(err.to_string().contains("line 1"));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
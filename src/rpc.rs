@@ -1,12 +1,22 @@
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
+use futures::stream::{SplitSink, SplitStream};
 use futures::{SinkExt, StreamExt};
+use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 use std::collections::HashMap;
-use tokio::sync::mpsc;
-use tokio_tungstenite::{connect_async, tungstenite::Message};
-
-#[derive(Debug, Clone)]
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::net::TcpStream;
+use tokio::sync::broadcast::{self, error::TryRecvError};
+use tokio::sync::{mpsc, watch, Notify};
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::http::{header::AUTHORIZATION, HeaderValue, Request};
+use tokio_tungstenite::{connect_async, tungstenite::Message, MaybeTlsStream, WebSocketStream};
+
+use crate::state::RefreshIntervals;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Block {
     pub number: u64,
     pub hash: String,
@@ -16,7 +26,7 @@ pub struct Block {
     pub gas_limit: u64,
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct RpcData {
     pub block_number: u64,
     pub gas_price_gwei: f64,
@@ -24,6 +34,28 @@ pub struct RpcData {
     pub client_version: String,
 }
 
+const RECENT_BLOCKS: usize = 30;
+
+/// How the client talks to the node, picked from the endpoint's URL scheme
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RpcMode {
+    /// ws:// or wss://: the node pushes each block over a newHeads subscription
+    Subscribe,
+    /// http:// or https://: poll for new blocks
+    Poll,
+}
+
+impl RpcMode {
+    pub fn from_endpoint(endpoint: &str) -> Result<Self> {
+        let scheme = endpoint.split("://").next().unwrap_or("").to_ascii_lowercase();
+        match scheme.as_str() {
+            "ws" | "wss" => Ok(RpcMode::Subscribe),
+            "http" | "https" => Ok(RpcMode::Poll),
+            _ => bail!("Unsupported RPC endpoint {} (expected ws://, wss://, http:// or https://)", endpoint),
+        }
+    }
+}
+
 #[derive(Serialize)]
 struct JsonRpcRequest {
     jsonrpc: &'static str,
@@ -47,40 +79,153 @@ struct SubscriptionParams {
 
 pub struct RpcClient {
     endpoint: String,
+    pub mode: RpcMode,
+    // Per-request limit for HTTP calls and WebSocket connects
+    timeout: Duration,
+    // Full Authorization header value (e.g. "Bearer <token>") for nodes behind an auth proxy
+    auth_header: Option<String>,
 }
 
 impl RpcClient {
-    pub fn new(endpoint: &str) -> Self {
-        Self {
+    pub fn new(endpoint: &str, auth_header: Option<String>, timeout: Duration) -> Result<Self> {
+        Ok(Self {
             endpoint: endpoint.to_string(),
-        }
+            mode: RpcMode::from_endpoint(endpoint)?,
+            timeout,
+            auth_header,
+        })
     }
 
-    /// Spawn a background task that subscribes to new blocks and sends updates
+    /// Spawn a task that follows new blocks, subscribing or polling depending on `mode`,
+    /// and sends each update to `tx`. Polling runs on the metrics interval from
+    /// `intervals`. When the connection drops, the error is sent and the task
+    /// reconnects after a second; `refresh` cuts that wait short and triggers a poll.
+    /// The task ends once `quit`'s sender is dropped, closing its WebSocket first.
     pub fn subscribe(
         &self,
-        tx: mpsc::Sender<RpcData>,
+        tx: mpsc::Sender<Result<RpcData, String>>,
+        refresh: Arc<Notify>,
+        mut intervals: watch::Receiver<RefreshIntervals>,
+        mut quit: broadcast::Receiver<()>,
     ) -> tokio::task::JoinHandle<()> {
         let endpoint = self.endpoint.clone();
+        let auth_header = self.auth_header.clone();
+        let mode = self.mode;
+        let timeout = self.timeout;
 
         tokio::spawn(async move {
+            let http = Client::builder().timeout(timeout).build().unwrap_or_default();
             loop {
-                if let Err(_) = run_subscription(&endpoint, &tx).await {
-                    // Reconnect after a brief delay on error
-                    tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+                let result = match mode {
+                    RpcMode::Subscribe => {
+                        let connected = tokio::select! {
+                            connected = connect_ws(&endpoint, auth_header.as_deref(), timeout) => connected,
+                            _ = quit.recv() => break,
+                        };
+                        match connected {
+                            Ok(ws_stream) => run_subscription(ws_stream, &tx, &mut quit).await,
+                            // Many providers serve HTTP on the same URL; poll there until it fails,
+                            // then try the WebSocket again
+                            Err(e) => tokio::select! {
+                                result = async {
+                                    match http_fallback(&http, &endpoint, auth_header.as_deref()).await {
+                                        Some(url) => {
+                                            let auth = auth_header.as_deref();
+                                            run_polling(&http, &url, auth, &tx, &refresh, &mut intervals).await
+                                        }
+                                        None => Err(e),
+                                    }
+                                } => result,
+                                _ = quit.recv() => break,
+                            },
+                        }
+                    }
+                    RpcMode::Poll => tokio::select! {
+                        result = run_polling(&http, &endpoint, auth_header.as_deref(), &tx, &refresh, &mut intervals) => result,
+                        _ = quit.recv() => break,
+                    },
+                };
+                // The subscription returns normally when told to quit
+                if matches!(quit.try_recv(), Err(TryRecvError::Closed)) {
+                    break;
+                }
+                let err = match result {
+                    Ok(()) => "connection closed".to_string(),
+                    Err(e) => format!("{:#}", e),
+                };
+                let _ = tx.send(Err(err)).await;
+                // Reconnect after a brief delay
+                tokio::select! {
+                    _ = tokio::time::sleep(tokio::time::Duration::from_secs(1)) => {}
+                    _ = refresh.notified() => {}
+                    _ = quit.recv() => break,
                 }
             }
         })
     }
 }
 
-async fn run_subscription(endpoint: &str, tx: &mpsc::Sender<RpcData>) -> Result<()> {
-    let (ws_stream, _) = connect_async(endpoint)
+/// WebSocket handshake request for `endpoint`, carrying `auth_header` as Authorization if set
+pub fn ws_request(endpoint: &str, auth_header: Option<&str>) -> Result<Request<()>> {
+    let mut request = endpoint
+        .into_client_request()
+        .with_context(|| format!("Invalid WebSocket endpoint {}", endpoint))?;
+    if let Some(auth) = auth_header {
+        let value = HeaderValue::from_str(auth).context("Invalid Authorization header value")?;
+        request.headers_mut().insert(AUTHORIZATION, value);
+    }
+    Ok(request)
+}
+
+type WsStream = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+async fn connect_ws(endpoint: &str, auth_header: Option<&str>, timeout: Duration) -> Result<WsStream> {
+    let (ws_stream, _) = tokio::time::timeout(timeout, connect_async(ws_request(endpoint, auth_header)?))
         .await
+        .context("Timed out connecting to WebSocket")?
         .context("Failed to connect to WebSocket")?;
+    Ok(ws_stream)
+}
+
+/// The http(s):// form of a ws(s):// endpoint
+fn http_equivalent(endpoint: &str) -> Option<String> {
+    let (scheme, rest) = endpoint.split_once("://")?;
+    match scheme.to_ascii_lowercase().as_str() {
+        "ws" => Some(format!("http://{}", rest)),
+        "wss" => Some(format!("https://{}", rest)),
+        _ => None,
+    }
+}
+
+/// The HTTP form of a WebSocket endpoint, if it answers JSON-RPC
+async fn http_fallback(http: &Client, endpoint: &str, auth_header: Option<&str>) -> Option<String> {
+    let url = http_equivalent(endpoint)?;
+    http_call(http, &url, auth_header, "eth_blockNumber", json!([])).await.ok()?;
+    Some(url)
+}
 
+/// Follow new blocks over `ws_stream` until it closes, or until `quit`'s sender is
+/// dropped, in which case the node is sent a Close frame first
+async fn run_subscription(
+    ws_stream: WsStream,
+    tx: &mpsc::Sender<Result<RpcData, String>>,
+    quit: &mut broadcast::Receiver<()>,
+) -> Result<()> {
     let (mut write, mut read) = ws_stream.split();
+    tokio::select! {
+        result = follow_blocks(&mut write, &mut read, tx) => result,
+        _ = quit.recv() => {
+            let _ = write.send(Message::Close(None)).await;
+            Ok(())
+        }
+    }
+}
 
+async fn follow_blocks(
+    write: &mut SplitSink<WsStream, Message>,
+    read: &mut SplitStream<WsStream>,
+    tx: &mpsc::Sender<Result<RpcData, String>>,
+) -> Result<()> {
     // Get initial data
     let mut data = RpcData::default();
 
@@ -144,11 +289,11 @@ async fn run_subscription(endpoint: &str, tx: &mpsc::Sender<RpcData>) -> Result<
 
     // Fetch initial blocks
     if data.block_number > 0 {
-        data.recent_blocks = fetch_blocks(&mut write, &mut read, data.block_number, 30).await?;
+        data.recent_blocks = fetch_blocks(write, read, data.block_number, RECENT_BLOCKS as u32).await?;
     }
 
     // Send initial data
-    let _ = tx.send(data.clone()).await;
+    let _ = tx.send(Ok(data.clone())).await;
 
     // Subscribe to new block headers
     let subscribe_req = JsonRpcRequest {
@@ -197,9 +342,9 @@ async fn run_subscription(endpoint: &str, tx: &mpsc::Sender<RpcData>) -> Result<
                                 // Update data
                                 data.block_number = number;
 
-                                // Add new block to front, keep max 30
+                                // Add new block to front, keep max RECENT_BLOCKS
                                 data.recent_blocks.insert(0, new_block);
-                                if data.recent_blocks.len() > 30 {
+                                if data.recent_blocks.len() > RECENT_BLOCKS {
                                     data.recent_blocks.pop();
                                 }
 
@@ -224,12 +369,12 @@ async fn run_subscription(endpoint: &str, tx: &mpsc::Sender<RpcData>) -> Result<
                                 write.send(Message::Text(serde_json::to_string(&gas_req)?)).await?;
 
                                 // Send update immediately
-                                let _ = tx.send(data.clone()).await;
+                                let _ = tx.send(Ok(data.clone())).await;
                             }
                         }
                     } else if let (Some(id), Some(result)) = (resp.id, resp.result) {
                         // Handle response to our requests
-                        if id >= 10000 && id < 110000 {
+                        if (10000..110000).contains(&id) {
                             // Block details response - update tx count for matching block
                             let block_num_suffix = (id - 10000) as u64;
                             let tx_count = result["transactions"]
@@ -240,7 +385,7 @@ async fn run_subscription(endpoint: &str, tx: &mpsc::Sender<RpcData>) -> Result<
                             if let Some(block) = data.recent_blocks.iter_mut().find(|b| b.number % 100000 == block_num_suffix) {
                                 block.tx_count = tx_count;
                             }
-                            let _ = tx.send(data.clone()).await;
+                            let _ = tx.send(Ok(data.clone())).await;
                         } else if id == 1001 {
                             // Gas price response
                             if let Some(hex) = result.as_str() {
@@ -304,33 +449,136 @@ where
     for i in 0..count {
         if let Some(result) = block_responses.get(&(100 + i)) {
             let block_num = start_block.saturating_sub(i as u64);
-            blocks.push(Block {
-                number: block_num,
-                hash: result["hash"].as_str().unwrap_or("0x0").to_string(),
-                tx_count: result["transactions"]
-                    .as_array()
-                    .map(|arr| arr.len())
-                    .unwrap_or(0),
-                timestamp: result["timestamp"]
-                    .as_str()
-                    .map(parse_hex_u64)
-                    .unwrap_or(0),
-                gas_used: result["gasUsed"]
-                    .as_str()
-                    .map(parse_hex_u64)
-                    .unwrap_or(0),
-                gas_limit: result["gasLimit"]
-                    .as_str()
-                    .map(parse_hex_u64)
-                    .unwrap_or(0),
-            });
+            blocks.push(parse_block(block_num, result));
         }
     }
 
     Ok(blocks)
 }
 
+/// Block from an eth_getBlockByNumber result (transaction hashes only)
+fn parse_block(number: u64, result: &Value) -> Block {
+    Block {
+        number,
+        hash: result["hash"].as_str().unwrap_or("0x0").to_string(),
+        tx_count: result["transactions"]
+            .as_array()
+            .map(|arr| arr.len())
+            .unwrap_or(0),
+        timestamp: result["timestamp"]
+            .as_str()
+            .map(parse_hex_u64)
+            .unwrap_or(0),
+        gas_used: result["gasUsed"]
+            .as_str()
+            .map(parse_hex_u64)
+            .unwrap_or(0),
+        gas_limit: result["gasLimit"]
+            .as_str()
+            .map(parse_hex_u64)
+            .unwrap_or(0),
+    }
+}
+
+/// Follow new blocks over plain HTTP JSON-RPC, for nodes without WebSocket enabled,
+/// polling on the metrics interval or when `refresh` is notified
+async fn run_polling(
+    http: &Client,
+    endpoint: &str,
+    auth_header: Option<&str>,
+    tx: &mpsc::Sender<Result<RpcData, String>>,
+    refresh: &Notify,
+    intervals: &mut watch::Receiver<RefreshIntervals>,
+) -> Result<()> {
+    let mut data = RpcData::default();
+    let mut ticker = tokio::time::interval(intervals.borrow_and_update().metrics);
+
+    loop {
+        tokio::select! {
+            _ = ticker.tick() => {}
+            _ = refresh.notified() => {}
+            // Poll once now and then keep to the new interval
+            Ok(()) = intervals.changed() => {
+                ticker = tokio::time::interval(intervals.borrow_and_update().metrics);
+                ticker.tick().await;
+            }
+        }
+
+        let block_number = http_call(http, endpoint, auth_header, "eth_blockNumber", json!([]))
+            .await?
+            .as_str()
+            .map(parse_hex_u64)
+            .unwrap_or(0);
+        if block_number <= data.block_number {
+            continue;
+        }
+
+        if data.client_version.is_empty() {
+            if let Ok(version) = http_call(http, endpoint, auth_header, "web3_clientVersion", json!([])).await {
+                data.client_version = version.as_str().unwrap_or("").to_string();
+            }
+        }
+        if let Ok(price) = http_call(http, endpoint, auth_header, "eth_gasPrice", json!([])).await {
+            data.gas_price_gwei = price.as_str().map(parse_hex_u64).unwrap_or(0) as f64 / 1_000_000_000.0;
+        }
+
+        // Fetch every block since the last poll (up to the table size), oldest first
+        let first = (data.block_number + 1).max(block_number.saturating_sub(RECENT_BLOCKS as u64 - 1));
+        for number in first..=block_number {
+            let params = json!([format!("0x{:x}", number), false]);
+            let result = http_call(http, endpoint, auth_header, "eth_getBlockByNumber", params).await?;
+            data.recent_blocks.insert(0, parse_block(number, &result));
+        }
+        data.recent_blocks.truncate(RECENT_BLOCKS);
+        data.block_number = block_number;
+
+        let _ = tx.send(Ok(data.clone())).await;
+    }
+}
+
+async fn http_call(http: &Client, endpoint: &str, auth_header: Option<&str>, method: &str, params: Value) -> Result<Value> {
+    let request = JsonRpcRequest {
+        jsonrpc: "2.0",
+        method: method.to_string(),
+        params,
+        id: 1,
+    };
+    let mut builder = http.post(endpoint).json(&request);
+    if let Some(auth) = auth_header {
+        builder = builder.header(AUTHORIZATION, auth);
+    }
+    let response: JsonRpcResponse = builder
+        .send()
+        .await
+        .with_context(|| format!("Failed to call {}", method))?
+        .json()
+        .await
+        .with_context(|| format!("Failed to parse {} response", method))?;
+    response.result.with_context(|| format!("No result for {}", method))
+}
+
 fn parse_hex_u64(hex: &str) -> u64 {
     let hex = hex.trim_start_matches("0x");
     u64::from_str_radix(hex, 16).unwrap_or(0)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_http_equivalent() {
+        assert_eq!(http_equivalent("ws://localhost:8081").as_deref(), Some("http://localhost:8081"));
+        assert_eq!(http_equivalent("WSS://rpc.example.com/v1").as_deref(), Some("https://rpc.example.com/v1"));
+        assert_eq!(http_equivalent("http://localhost:8080"), None);
+    }
+
+    #[test]
+    fn test_mode_from_endpoint_scheme() {
+        assert_eq!(RpcMode::from_endpoint("ws://localhost:8081").unwrap(), RpcMode::Subscribe);
+        assert_eq!(RpcMode::from_endpoint("WSS://rpc.example.com").unwrap(), RpcMode::Subscribe);
+        assert_eq!(RpcMode::from_endpoint("http://localhost:8080").unwrap(), RpcMode::Poll);
+        assert_eq!(RpcMode::from_endpoint("https://rpc.example.com").unwrap(), RpcMode::Poll);
+        assert!(RpcMode::from_endpoint("localhost:8080").is_err());
+    }
+}
@@ -0,0 +1,181 @@
+use anyhow::{Context, Result};
+use std::fmt::Write as _;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::{broadcast, watch};
+use tokio::task::JoinHandle;
+
+use crate::state::Snapshot;
+
+const MAX_REQUEST_SIZE: usize = 8192;
+const PROMETHEUS_CONTENT_TYPE: &str = "text/plain; version=0.0.4";
+
+/// What a `StateServer` exposes
+#[derive(Debug, Clone, Copy)]
+pub enum Format {
+    /// The snapshot as JSON at `/state`
+    Json,
+    /// Derived gauges in Prometheus text exposition format at `/metrics`
+    Prometheus,
+}
+
+impl Format {
+    fn path(self) -> &'static str {
+        match self {
+            Format::Json => "/state",
+            Format::Prometheus => "/metrics",
+        }
+    }
+}
+
+/// Minimal HTTP server exposing the latest state snapshot
+pub struct StateServer {
+    listener: TcpListener,
+    format: Format,
+}
+
+impl StateServer {
+    pub async fn bind(addr: &str, format: Format) -> Result<Self> {
+        let listener = TcpListener::bind(addr)
+            .await
+            .with_context(|| format!("Failed to bind {} server to {}", format.path(), addr))?;
+        Ok(Self { listener, format })
+    }
+
+    /// Serve requests until `quit`'s sender is dropped
+    pub fn spawn(self, snapshots: watch::Receiver<Snapshot>, mut quit: broadcast::Receiver<()>) -> JoinHandle<()> {
+        let format = self.format;
+        tokio::spawn(async move {
+            loop {
+                let accepted = tokio::select! {
+                    accepted = self.listener.accept() => accepted,
+                    _ = quit.recv() => break,
+                };
+                let Ok((stream, _)) = accepted else {
+                    continue;
+                };
+                let snapshots = snapshots.clone();
+                tokio::spawn(async move {
+                    let _ = handle_connection(stream, format, snapshots).await;
+                });
+            }
+        })
+    }
+}
+
+async fn handle_connection(mut stream: TcpStream, format: Format, snapshots: watch::Receiver<Snapshot>) -> Result<()> {
+    // Read until the end of the headers; the body (if any) is ignored
+    let mut request = Vec::new();
+    let mut buf = [0u8; 1024];
+    while !request.windows(4).any(|w| w == b"\r\n\r\n") && request.len() < MAX_REQUEST_SIZE {
+        let n = stream.read(&mut buf).await?;
+        if n == 0 {
+            break;
+        }
+        request.extend_from_slice(&buf[..n]);
+    }
+
+    let request = String::from_utf8_lossy(&request);
+    let mut request_line = request.lines().next().unwrap_or("").split_whitespace();
+    let method = request_line.next().unwrap_or("");
+    let path = request_line.next().unwrap_or("");
+    // Ignore any query string
+    let path = path.split('?').next().unwrap_or("");
+
+    let (status, content_type, body) = match method {
+        "GET" if path == format.path() => {
+            let snapshot = snapshots.borrow().clone();
+            match format {
+                Format::Json => ("200 OK", "application/json", serde_json::to_string(&snapshot)?),
+                Format::Prometheus => ("200 OK", PROMETHEUS_CONTENT_TYPE, prometheus_text(&snapshot)),
+            }
+        }
+        "GET" => ("404 Not Found", "text/plain", "not found\n".to_string()),
+        _ => ("405 Method Not Allowed", "text/plain", "method not allowed\n".to_string()),
+    };
+
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        content_type,
+        body.len(),
+        body
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await?;
+    Ok(())
+}
+
+/// Render derived values as Prometheus gauges (base units: seconds, bytes, ratios)
+fn prometheus_text(snapshot: &Snapshot) -> String {
+    let mut out = String::new();
+    let mut gauge = |name: &str, help: &str, value: f64| {
+        let _ = writeln!(out, "# HELP monad_monitor_{} {}", name, help);
+        let _ = writeln!(out, "# TYPE monad_monitor_{} gauge", name);
+        let _ = writeln!(out, "monad_monitor_{} {}", name, value);
+    };
+
+    gauge("block_height", "Latest block seen by the node.", snapshot.block_height as f64);
+    if let Some(age) = snapshot.block_age_secs {
+        gauge("block_age_seconds", "Seconds since the last new block was seen.", age);
+    }
+    gauge(
+        "block_difference",
+        "Blocks the node is behind the external reference RPC (negative if ahead).",
+        snapshot.block_difference as f64,
+    );
+    if let Some(age) = snapshot.external_block_age_secs {
+        gauge("external_block_age_seconds", "Seconds since the external reference RPC last answered.", age);
+    }
+    gauge(
+        "synced",
+        "Whether statesync has reached its target (1) or not (0).",
+        f64::from(u8::from(snapshot.sync_status == "synced")),
+    );
+    gauge("sync_ratio", "Statesync progress towards its target (1 when synced).", snapshot.sync_pct / 100.0);
+    gauge("tps", "Transactions per second over the recent sample window.", snapshot.tps);
+    gauge("tps_smoothed", "Exponential moving average of TPS.", snapshot.tps_ema);
+    gauge("tps_peak", "Highest TPS seen since the monitor started.", snapshot.tps_peak);
+    gauge("tps_peak_recent", "Highest TPS over the last 10 minutes.", snapshot.tps_peak_recent);
+    gauge("peers", "Connected peer count.", snapshot.peers as f64);
+    gauge("latency_p99_seconds", "p99 network latency in seconds.", snapshot.latency_p99_ms as f64 / 1000.0);
+    gauge("pending_txs", "Transactions in the pending pool.", snapshot.pending_txs as f64);
+    gauge("upstream_validators", "Upstream validator count.", snapshot.upstream_validators as f64);
+    gauge("cpu_usage_ratio", "Host CPU usage over the last sample interval.", snapshot.cpu_pct / 100.0);
+    gauge("load1", "Host 1-minute load average.", snapshot.load_1);
+    gauge("memory_used_ratio", "Host memory in use.", snapshot.mem_pct / 100.0);
+    gauge("swap_used_ratio", "Host swap in use.", snapshot.swap_pct / 100.0);
+    if let Some(disk_pct) = snapshot.disk_pct {
+        gauge("disk_used_ratio", "Triedb disk capacity in use.", disk_pct / 100.0);
+    }
+    gauge("disk_read_bytes_per_second", "Triedb device read throughput.", snapshot.disk_read_rate);
+    gauge("disk_write_bytes_per_second", "Triedb device write throughput.", snapshot.disk_write_rate);
+    gauge("net_rx_bytes_per_second", "Host network receive rate.", snapshot.net_rx_rate);
+    gauge("net_tx_bytes_per_second", "Host network transmit rate.", snapshot.net_tx_rate);
+    if let Some(lag) = snapshot.finalized_lag {
+        gauge("finalized_lag_blocks", "Blocks between the latest and finalized block.", lag as f64);
+    }
+    gauge("alerts_firing", "Number of threshold alerts currently firing.", snapshot.alerts.len() as f64);
+
+    let _ = writeln!(out, "# HELP monad_monitor_service_up Whether a monitored systemd unit is active.");
+    let _ = writeln!(out, "# TYPE monad_monitor_service_up gauge");
+    for service in &snapshot.services {
+        let _ = writeln!(
+            out,
+            "monad_monitor_service_up{{unit=\"{}\"}} {}",
+            service.name.replace('\\', "\\\\").replace('"', "\\\""),
+            u8::from(service.active)
+        );
+    }
+
+    let _ = writeln!(
+        out,
+        "# HELP monad_monitor_source_up Whether a data feed last succeeded and is neither stale nor frozen."
+    );
+    let _ = writeln!(out, "# TYPE monad_monitor_source_up gauge");
+    for source in &snapshot.sources {
+        let _ = writeln!(out, "monad_monitor_source_up{{source=\"{}\"}} {}", source.name, u8::from(source.healthy));
+    }
+
+    out
+}
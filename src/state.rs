@@ -1,12 +1,27 @@
-use std::collections::VecDeque;
-use std::time::{Duration, Instant};
+use ratatui::layout::{Position, Rect};
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
+use crate::alerts::{self, AlertEvent, AlertKind, Alerts, Transition};
+use crate::config::{Config, NodeConfig, MIN_REFRESH_INTERVAL_MS};
+use crate::history::SavedHistory;
 use crate::metrics::PrometheusMetrics;
 use crate::rpc::{Block, RpcData};
 use crate::system::SystemData;
 
-const TPS_HISTORY_SIZE: usize = 300; // 5 minutes of history (fills wide terminals)
-const SAMPLE_HISTORY_SIZE: usize = 10; // Keep last 10 samples for TPS calculation
+const TPS_SAMPLE_WINDOW_MS: u64 = 10_000; // TPS is the rate over the last 10s of samples
+const TPS_PEAK_WINDOW_MS: u64 = 10 * 60_000; // The headline peak covers the last 10 minutes
+const SYSTEM_HISTORY_SIZE: usize = 120; // 10 minutes of 5s system samples
+const MAX_PLAUSIBLE_TPS: f64 = 1_000_000.0; // Larger per-sample jumps are treated as counter glitches
+const MAX_PLAUSIBLE_NET_RATE: f64 = 12_500_000_000.0; // 100 Gbit/s per interface
+const RPC_EXPECTED_INTERVAL_MS: u64 = 1000; // newHeads arrive sub-second on a live node
+const STALE_AFTER_INTERVALS: u32 = 3; // Missed refreshes before a source counts as stale
+const ERROR_LOG_SIZE: usize = 50;
+const METRICS_FROZEN_AFTER: u32 = 5; // Fetches with an unchanged exporter timestamp before warning
+const METRICS_TIMESTAMP_LAG: Duration = Duration::from_secs(10); // Exporter sample age before it's distrusted
+const MAX_REFRESH_INTERVAL: Duration = Duration::from_secs(300);
+const EXTERNAL_BLOCK_STALE_AFTER: Duration = Duration::from_secs(30);
 
 #[derive(Debug, Clone, Copy, PartialEq, Default)]
 pub enum Theme {
@@ -17,6 +32,201 @@ pub enum Theme {
     Matrix,     // Green on black hacker style
     Ocean,      // Blue tones
     Christmas,  // Festive red and green
+    Dracula,    // Dracula palette (purple, cyan, green)
+    Solarized,  // Solarized Dark
+    HighContrast, // White on black with saturated accents, for low vision / bright screens
+    Custom,     // Palette from the config file's [custom_theme]
+}
+
+impl Theme {
+    /// Every theme in cycle order; number keys 1-9 and 0 pick by position
+    pub const ALL: [Theme; 10] = [
+        Theme::Gray,
+        Theme::Light,
+        Theme::Monad,
+        Theme::Matrix,
+        Theme::Ocean,
+        Theme::Christmas,
+        Theme::Dracula,
+        Theme::Solarized,
+        Theme::HighContrast,
+        Theme::Custom,
+    ];
+
+    /// Theme at zero-based `index` in `ALL`
+    pub fn from_index(index: usize) -> Option<Theme> {
+        Theme::ALL.get(index).copied()
+    }
+
+    /// Parse a theme by its `name()`, ignoring case
+    pub fn from_name(name: &str) -> Option<Theme> {
+        Theme::ALL.into_iter().find(|t| t.name().eq_ignore_ascii_case(name))
+    }
+
+    /// Comma-separated list of every theme name, for error messages
+    pub fn names() -> String {
+        Theme::ALL.map(Theme::name).join(", ")
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Theme::Gray => "gray",
+            Theme::Light => "light",
+            Theme::Monad => "monad",
+            Theme::Matrix => "matrix",
+            Theme::Ocean => "ocean",
+            Theme::Christmas => "christmas",
+            Theme::Dracula => "dracula",
+            Theme::Solarized => "solarized",
+            Theme::HighContrast => "high-contrast",
+            Theme::Custom => "custom",
+        }
+    }
+
+    /// Whether health values carry ✓ / ! / ✗ markers, so status doesn't depend on
+    /// telling green, yellow and red apart
+    pub fn uses_symbols(self) -> bool {
+        self == Theme::HighContrast
+    }
+}
+
+/// Which metric the graph panel is currently showing
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum GraphMetric {
+    #[default]
+    Tps,
+    Memory,
+    Cpu,
+    Latency,
+    Network,
+    Gas,
+    Pool,
+}
+
+/// Background feeds, tracked separately so one that silently stops can be flagged
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DataSource {
+    Metrics,
+    Rpc,
+    System,
+}
+
+impl DataSource {
+    pub const ALL: [DataSource; 3] = [DataSource::Metrics, DataSource::Rpc, DataSource::System];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            DataSource::Metrics => "metrics",
+            DataSource::Rpc => "rpc",
+            DataSource::System => "system",
+        }
+    }
+
+    fn stale_after(self, intervals: RefreshIntervals) -> Duration {
+        let interval = match self {
+            DataSource::Metrics => intervals.metrics,
+            DataSource::Rpc => Duration::from_millis(RPC_EXPECTED_INTERVAL_MS),
+            DataSource::System => intervals.system,
+        };
+        interval * STALE_AFTER_INTERVALS
+    }
+}
+
+/// How often the metrics and system fetchers run
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RefreshIntervals {
+    pub metrics: Duration,
+    pub system: Duration,
+}
+
+impl RefreshIntervals {
+    /// The configured intervals, kept within the allowed range since the config file
+    /// isn't checked like the flags (a zero interval would panic the fetchers)
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            metrics: clamp_interval(Duration::from_millis(config.metrics_interval_ms)),
+            system: clamp_interval(Duration::from_millis(config.system_interval_ms)),
+        }
+    }
+
+    /// Both intervals halved (`faster`) or doubled, each kept within the allowed range
+    pub fn scaled(self, faster: bool) -> Self {
+        let scale = |interval: Duration| clamp_interval(if faster { interval / 2 } else { interval * 2 });
+        Self { metrics: scale(self.metrics), system: scale(self.system) }
+    }
+}
+
+fn clamp_interval(interval: Duration) -> Duration {
+    interval.clamp(Duration::from_millis(MIN_REFRESH_INTERVAL_MS), MAX_REFRESH_INTERVAL)
+}
+
+/// Clickable screen regions, recorded on each draw for mouse hit-testing
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HitAreas {
+    pub theme_label: Rect,
+    /// One line per row of the blocks table, starting at recent_blocks()[0]
+    pub block_rows: Rect,
+}
+
+/// Point-in-time view of the derived state, for structured export
+#[derive(Debug, Clone, Serialize)]
+pub struct Snapshot {
+    pub timestamp: u64,
+    pub node_id: String,
+    pub block_height: u64,
+    pub block_age_secs: Option<f64>,
+    pub external_block: u64,
+    pub external_block_age_secs: Option<f64>,
+    pub block_difference: i64,
+    pub sync_status: &'static str,
+    pub sync_pct: f64,
+    pub tps: f64,
+    pub tps_ema: f64,
+    pub tps_peak: f64,
+    pub tps_peak_recent: f64,
+    pub tps_trend: i8,
+    pub peers: u64,
+    pub peers_trend: i8,
+    pub latency_p99_ms: u64,
+    pub latency_trend: i8,
+    pub pending_txs: u64,
+    pub pending_trend: i8,
+    pub upstream_validators: u64,
+    pub validators_trend: i8,
+    pub consensus_round: u64,
+    pub consensus_view: u64,
+    pub gas_price_gwei: f64,
+    pub cpu_pct: f64,
+    pub load_1: f64,
+    pub load_5: f64,
+    pub load_15: f64,
+    pub mem_pct: f64,
+    pub mem_used_gb: f64,
+    pub swap_pct: f64,
+    pub disk_pct: Option<f64>,
+    pub disk_read_rate: f64,
+    pub disk_write_rate: f64,
+    pub net_rx_rate: f64,
+    pub net_tx_rate: f64,
+    pub finalized_lag: Option<u64>,
+    pub verified_gap: Option<u64>,
+    pub history_count: Option<u64>,
+    pub services: Vec<ServiceStatus>,
+    pub sources: Vec<SourceStatus>,
+    pub alerts: Vec<String>,
+    pub last_error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ServiceStatus {
+    pub name: String,
+    pub active: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SourceStatus {
+    pub name: &'static str,
+    pub healthy: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -26,6 +236,8 @@ struct TxSample {
 }
 
 pub struct AppState {
+    pub config: Config,
+
     // Current data
     pub metrics: PrometheusMetrics,
     pub rpc_data: RpcData,
@@ -33,61 +245,196 @@ pub struct AppState {
 
     // TPS calculation
     tx_samples: VecDeque<TxSample>,
+    // Consecutive fetches whose tx_commits timestamp didn't advance
+    metrics_frozen_fetches: u32,
     pub tps: f64,
+    // Exponential moving average of tps, for a readable headline number
+    pub tps_ema: f64,
     pub tps_history: VecDeque<u64>,
+    // Metrics timestamp of each tps_history entry, to label the span the graph covers
+    tps_history_ms: VecDeque<u64>,
+    // Capacity of tps_history and the other per-scrape histories (config.tps_window)
+    tps_window: usize,
+    // Current fetch intervals, shared by every node and changed with + / -
+    pub refresh: RefreshIntervals,
+    // All-time peak, since the monitor started (or from saved history)
     pub tps_peak: f64,
+    // Candidates for the peak over TPS_PEAK_WINDOW_MS as (metrics timestamp, tps),
+    // with decreasing tps so the front is always the windowed peak
+    tps_recent_peaks: VecDeque<(u64, f64)>,
     tps_prev: f64,
 
-    // Timing
-    pub last_update: Instant,
+    // Timing (last successful update per source; None until the first one)
+    metrics_updated_at: Option<Instant>,
+    rpc_updated_at: Option<Instant>,
+    system_updated_at: Option<Instant>,
+    // Whether each source's latest fetch succeeded; None until it has reported
+    metrics_ok: Option<bool>,
+    rpc_ok: Option<bool>,
+    system_ok: Option<bool>,
+    // When the reference RPC last answered; its block is carried over between failures
+    external_block_at: Option<Instant>,
     pub last_block_time: Option<Instant>,
     last_block_number: u64,
 
-    // Latency tracking
+    // Latency and pending pool tracking
+    pub latency_history: VecDeque<u64>,
+    pub pending_history: VecDeque<u64>,
     latency_prev: u64,
     peers_prev: u64,
+    pending_prev: u64,
+    validators_prev: u64,
 
     // Network rate tracking
-    net_rx_prev: u64,
-    net_tx_prev: u64,
+    net_prev: HashMap<String, (u64, u64)>, // per-interface (rx, tx) bytes
+    net_prev_at: Option<Instant>,
     pub net_rx_rate: f64, // bytes per second
     pub net_tx_rate: f64,
+    pub net_rx_history: VecDeque<u64>,
+    pub net_tx_history: VecDeque<u64>,
+
+    // System resource history (percent, one sample per system update)
+    pub mem_history: VecDeque<u64>,
+    pub cpu_history: VecDeque<u64>,
 
-    // Error tracking
+    // Error tracking: the latest for the footer, plus a history for the error log panel
     pub last_error: Option<String>,
+    pub error_log: VecDeque<(Instant, String)>,
+    pub show_error_log: bool,
+    // Informational message shown in the footer for a while (not cleared by updates)
+    notice: Option<(Instant, String)>,
+
+    // Threshold alerts currently firing
+    pub alerts: Alerts,
+    // Terminal bell: when it last rang, and whether the operator acknowledged the current alerts
+    last_bell: Option<Instant>,
+    pub bell_silenced: bool,
 
     // UI theme
     pub theme: Theme,
-}
 
-impl Default for AppState {
-    fn default() -> Self {
-        Self::new()
-    }
+    // Minimal two-line layout instead of the full dashboard
+    pub compact: bool,
+
+    // Metric shown in the graph panel
+    pub graph_metric: GraphMetric,
+
+    // Blocks table selection (block number, so it stays put as new blocks arrive)
+    // and detail popup
+    pub selected_block: Option<u64>,
+    // Show block times as wall-clock HH:MM:SS instead of "Ns ago"
+    pub absolute_time: bool,
+    // Header shows the all-time peak TPS instead of the last 10 minutes'
+    pub all_time_peak: bool,
+    // With several nodes, show this one next to the following one instead of in full
+    pub split_view: bool,
+    pub show_block_detail: bool,
+
+    // Footer is showing "Quit? (y/n)" (only with confirm_quit)
+    pub confirming_quit: bool,
+
+    // Keybinding / theme list overlay
+    pub show_help: bool,
+
+    // Where the last frame put clickable elements
+    pub hit_areas: HitAreas,
 }
 
 impl AppState {
-    pub fn new() -> Self {
+    pub fn new(config: Config) -> Self {
+        let tps_window = config.tps_window.max(1);
+        let refresh = RefreshIntervals::from_config(&config);
+        // An explicit choice wins; otherwise a configured palette is what the operator
+        // wants to see first
+        let theme = match config.theme.as_deref().and_then(Theme::from_name) {
+            Some(theme) => theme,
+            None if config.custom_theme.is_some() => Theme::Custom,
+            None => Theme::Gray,
+        };
         Self {
+            compact: config.compact,
+            config,
             metrics: PrometheusMetrics::default(),
             rpc_data: RpcData::default(),
             system: SystemData::default(),
-            tx_samples: VecDeque::with_capacity(SAMPLE_HISTORY_SIZE),
+            tx_samples: VecDeque::new(),
+            metrics_frozen_fetches: 0,
             tps: 0.0,
-            tps_history: VecDeque::with_capacity(TPS_HISTORY_SIZE),
+            tps_ema: 0.0,
+            tps_history: VecDeque::with_capacity(tps_window),
+            tps_history_ms: VecDeque::with_capacity(tps_window),
+            tps_window,
+            refresh,
             tps_peak: 0.0,
+            tps_recent_peaks: VecDeque::new(),
             tps_prev: 0.0,
-            last_update: Instant::now(),
+            metrics_updated_at: None,
+            rpc_updated_at: None,
+            system_updated_at: None,
+            metrics_ok: None,
+            rpc_ok: None,
+            system_ok: None,
+            external_block_at: None,
             last_block_time: None,
             last_block_number: 0,
+            latency_history: VecDeque::with_capacity(tps_window),
+            pending_history: VecDeque::with_capacity(tps_window),
             latency_prev: 0,
             peers_prev: 0,
-            net_rx_prev: 0,
-            net_tx_prev: 0,
+            pending_prev: 0,
+            validators_prev: 0,
+            net_prev: HashMap::new(),
+            net_prev_at: None,
             net_rx_rate: 0.0,
             net_tx_rate: 0.0,
+            net_rx_history: VecDeque::with_capacity(SYSTEM_HISTORY_SIZE),
+            net_tx_history: VecDeque::with_capacity(SYSTEM_HISTORY_SIZE),
+            mem_history: VecDeque::with_capacity(SYSTEM_HISTORY_SIZE),
+            cpu_history: VecDeque::with_capacity(SYSTEM_HISTORY_SIZE),
             last_error: None,
-            theme: Theme::Gray,
+            error_log: VecDeque::with_capacity(ERROR_LOG_SIZE),
+            show_error_log: false,
+            notice: None,
+            alerts: Alerts::default(),
+            last_bell: None,
+            bell_silenced: false,
+            theme,
+            graph_metric: GraphMetric::Tps,
+            selected_block: None,
+            absolute_time: false,
+            all_time_peak: false,
+            split_view: false,
+            show_block_detail: false,
+            confirming_quit: false,
+            show_help: false,
+            hit_areas: HitAreas::default(),
+        }
+    }
+
+    /// State for a remote node from `config.nodes`, labelled with its configured name
+    pub fn remote(config: &Config, node: &NodeConfig) -> Self {
+        let mut state = Self::new(Config { rpc_url: node.rpc_url.clone(), ..config.clone() });
+        state.system.node_id = node.name.clone();
+        state
+    }
+
+    /// Carry display choices over when focus moves to another node
+    pub fn take_view_from(&mut self, other: &AppState) {
+        self.theme = other.theme;
+        self.compact = other.compact;
+        self.graph_metric = other.graph_metric;
+        self.absolute_time = other.absolute_time;
+        self.all_time_peak = other.all_time_peak;
+        self.split_view = other.split_view;
+        self.show_help = other.show_help;
+    }
+
+    /// Remote nodes have no system fetch of their own; they share the local node's
+    /// reference block so their block difference still works
+    pub fn update_reference_block(&mut self, external_block: u64) {
+        if external_block > 0 {
+            self.system.external_block = external_block;
+            self.external_block_at = Some(Instant::now());
         }
     }
 
@@ -98,21 +445,139 @@ impl AppState {
             Theme::Monad => Theme::Matrix,
             Theme::Matrix => Theme::Ocean,
             Theme::Ocean => Theme::Christmas,
-            Theme::Christmas => Theme::Gray,
+            Theme::Christmas => Theme::Dracula,
+            Theme::Dracula => Theme::Solarized,
+            Theme::Solarized => Theme::HighContrast,
+            // The custom palette only joins the cycle when one is configured
+            Theme::HighContrast if self.config.custom_theme.is_some() => Theme::Custom,
+            Theme::HighContrast | Theme::Custom => Theme::Gray,
         };
     }
 
     pub fn theme_name(&self) -> &'static str {
-        match self.theme {
-            Theme::Gray => "gray",
-            Theme::Light => "light",
-            Theme::Monad => "monad",
-            Theme::Matrix => "matrix",
-            Theme::Ocean => "ocean",
-            Theme::Christmas => "christmas",
+        self.theme.name()
+    }
+
+    /// Jump to the theme at zero-based `index`; the custom slot needs a configured palette
+    pub fn select_theme(&mut self, index: usize) {
+        match Theme::from_index(index) {
+            Some(Theme::Custom) if self.config.custom_theme.is_none() => {}
+            Some(theme) => self.theme = theme,
+            None => {}
+        }
+    }
+
+    /// Handle `q`: true if the app should exit now, otherwise start asking for confirmation
+    pub fn request_quit(&mut self) -> bool {
+        if self.config.confirm_quit && !self.confirming_quit {
+            self.confirming_quit = true;
+            return false;
+        }
+        true
+    }
+
+    pub fn toggle_help(&mut self) {
+        self.show_help = !self.show_help;
+    }
+
+    pub fn toggle_absolute_time(&mut self) {
+        self.absolute_time = !self.absolute_time;
+    }
+
+    pub fn toggle_split_view(&mut self) {
+        self.split_view = !self.split_view;
+    }
+
+    pub fn toggle_peak_window(&mut self) {
+        self.all_time_peak = !self.all_time_peak;
+    }
+
+    pub fn toggle_compact(&mut self) {
+        self.compact = !self.compact;
+    }
+
+    pub fn cycle_graph(&mut self) {
+        self.graph_metric = match self.graph_metric {
+            GraphMetric::Tps => GraphMetric::Memory,
+            GraphMetric::Memory => GraphMetric::Cpu,
+            GraphMetric::Cpu => GraphMetric::Latency,
+            GraphMetric::Latency => GraphMetric::Network,
+            GraphMetric::Network => GraphMetric::Gas,
+            GraphMetric::Gas => GraphMetric::Pool,
+            GraphMetric::Pool => GraphMetric::Tps,
+        };
+    }
+
+    pub fn graph_name(&self) -> &'static str {
+        match self.graph_metric {
+            GraphMetric::Tps => "TPS",
+            GraphMetric::Memory => "MEMORY %",
+            GraphMetric::Cpu => "CPU %",
+            GraphMetric::Latency if self.config.graph.latency_log_scale => "LATENCY p99 (log)",
+            GraphMetric::Latency => "LATENCY p99",
+            GraphMetric::Network => "NET",
+            GraphMetric::Gas => "GAS USED % (recent blocks)",
+            GraphMetric::Pool => "PENDING TXS",
+        }
+    }
+
+    pub fn select_next_block(&mut self) {
+        let count = self.recent_blocks().len();
+        if count == 0 {
+            return;
+        }
+        let index = match self.selected_index() {
+            Some(i) => (i + 1).min(count - 1),
+            None => 0,
+        };
+        self.selected_block = Some(self.recent_blocks()[index].number);
+    }
+
+    pub fn select_prev_block(&mut self) {
+        if let Some(i) = self.selected_index() {
+            self.selected_block = Some(self.recent_blocks()[i.saturating_sub(1)].number);
+        }
+    }
+
+    /// Toggle the detail popup for the selected block
+    pub fn toggle_block_detail(&mut self) {
+        self.show_block_detail = self.selected_block.is_some() && !self.show_block_detail;
+    }
+
+    /// Handle a left click: the footer theme label cycles themes, a blocks table row
+    /// is selected (clicking the selected row again opens its details)
+    pub fn click(&mut self, column: u16, row: u16) {
+        let position = Position::new(column, row);
+        if self.hit_areas.theme_label.contains(position) {
+            self.toggle_theme();
+        } else if self.hit_areas.block_rows.contains(position) {
+            let index = (row - self.hit_areas.block_rows.y) as usize;
+            let Some(number) = self.recent_blocks().get(index).map(|b| b.number) else {
+                return;
+            };
+            if self.selected_block == Some(number) {
+                self.toggle_block_detail();
+            } else {
+                self.selected_block = Some(number);
+            }
         }
     }
 
+    /// Row of the selected block in the table, None once it has scrolled out of it
+    fn selected_index(&self) -> Option<usize> {
+        let number = self.selected_block?;
+        self.recent_blocks().iter().position(|b| b.number == number)
+    }
+
+    pub fn selected_block(&self) -> Option<&Block> {
+        self.selected_index().map(|i| &self.recent_blocks()[i])
+    }
+
+    /// Hash of the selected block, for copying with `y`
+    pub fn selected_hash(&self) -> Option<&str> {
+        self.selected_block().map(|b| b.hash.as_str())
+    }
+
     pub fn update_metrics(&mut self, metrics: PrometheusMetrics) {
         // Track new block
         if metrics.block_num > self.last_block_number {
@@ -120,6 +585,15 @@ impl AppState {
             self.last_block_number = metrics.block_num;
         }
 
+        // The endpoint can keep answering with a stalled exporter's last values
+        if metrics.tx_commits_timestamp_ms > 0
+            && metrics.tx_commits_timestamp_ms <= self.metrics.tx_commits_timestamp_ms
+        {
+            self.metrics_frozen_fetches += 1;
+        } else {
+            self.metrics_frozen_fetches = 0;
+        }
+
         // Add TX sample for TPS calculation
         if metrics.tx_commits_timestamp_ms > 0 {
             let sample = TxSample {
@@ -127,29 +601,23 @@ impl AppState {
                 timestamp_ms: metrics.tx_commits_timestamp_ms,
             };
 
-            // Only add if timestamp is newer
-            if self
-                .tx_samples
-                .back()
-                .map(|s| sample.timestamp_ms > s.timestamp_ms)
-                .unwrap_or(true)
-            {
-                self.tx_samples.push_back(sample);
-                if self.tx_samples.len() > SAMPLE_HISTORY_SIZE {
-                    self.tx_samples.pop_front();
-                }
-            }
+            self.push_tx_sample(sample);
         }
 
         // Calculate TPS from samples
         self.calculate_tps();
 
-        // Track latency and peers for trend
+        // Track latency, peers, pool depth and validators for trend
         self.latency_prev = self.metrics.latency_p99_ms;
         self.peers_prev = self.metrics.peer_count;
+        self.pending_prev = self.metrics.pending_txs;
+        self.validators_prev = self.metrics.upstream_validators;
+        push_capped(&mut self.latency_history, metrics.latency_p99_ms, self.tps_window);
+        push_capped(&mut self.pending_history, metrics.pending_txs, self.tps_window);
 
         self.metrics = metrics;
-        self.last_update = Instant::now();
+        self.metrics_updated_at = Some(Instant::now());
+        self.metrics_ok = Some(true);
         self.last_error = None;
     }
 
@@ -163,24 +631,106 @@ impl AppState {
         }
 
         self.rpc_data = rpc_data;
+        self.rpc_updated_at = Some(Instant::now());
+        self.rpc_ok = Some(true);
     }
 
-    pub fn update_system(&mut self, system: SystemData) {
-        // Calculate network rates (bytes per second)
-        // System updates every 5 seconds
-        const UPDATE_INTERVAL_SECS: f64 = 5.0;
+    pub fn update_system(&mut self, mut system: SystemData) {
+        if let Some(notice) = system.notice.take() {
+            self.set_notice(notice);
+        }
 
-        if self.net_rx_prev > 0 && system.net_rx_bytes > self.net_rx_prev {
-            self.net_rx_rate = (system.net_rx_bytes - self.net_rx_prev) as f64 / UPDATE_INTERVAL_SECS;
+        // Say why the block difference stopped updating, once per outage
+        if let Some(error) = &system.external_error {
+            if self.system.external_error.is_none() {
+                self.set_notice(format!("reference RPC {} unreachable: {}", self.config.external_rpc_url(), error));
+            }
         }
-        if self.net_tx_prev > 0 && system.net_tx_bytes > self.net_tx_prev {
-            self.net_tx_rate = (system.net_tx_bytes - self.net_tx_prev) as f64 / UPDATE_INTERVAL_SECS;
+
+        // One failed reference request shouldn't blank the block difference
+        if system.external_block > 0 {
+            self.external_block_at = Some(Instant::now());
+        } else {
+            system.external_block = self.system.external_block;
         }
 
-        self.net_rx_prev = system.net_rx_bytes;
-        self.net_tx_prev = system.net_tx_bytes;
+        // Divide by the real time between samples; fetch latency makes it drift from 5s
+        let now = Instant::now();
+        let elapsed_secs = self
+            .net_prev_at
+            .map(|at| now.duration_since(at).as_secs_f64())
+            .unwrap_or(0.0);
+        self.update_net_rates(&system.net_interfaces, elapsed_secs);
+        self.net_prev_at = Some(now);
+
+        push_capped(&mut self.net_rx_history, self.net_rx_rate as u64, SYSTEM_HISTORY_SIZE);
+        push_capped(&mut self.net_tx_history, self.net_tx_rate as u64, SYSTEM_HISTORY_SIZE);
+
+        push_capped(&mut self.mem_history, system.memory_used_pct.round() as u64, SYSTEM_HISTORY_SIZE);
+        if system.cpu_measured {
+            push_capped(&mut self.cpu_history, system.cpu_usage_pct.round() as u64, SYSTEM_HISTORY_SIZE);
+        }
 
         self.system = system;
+        self.system_updated_at = Some(now);
+        self.system_ok = Some(true);
+    }
+
+    /// Calculate network rates (bytes per second) from per-interface counters
+    /// sampled `elapsed_secs` apart (0 for the first sample, which only sets a baseline)
+    fn update_net_rates(&mut self, interfaces: &HashMap<String, (u64, u64)>, elapsed_secs: f64) {
+        // Only interfaces present in both samples contribute. New interfaces,
+        // counters that went backwards (wrap / reset) and implausible jumps just
+        // re-baseline, so one flapping interface can't corrupt the aggregate.
+        let mut rx_delta: u64 = 0;
+        let mut tx_delta: u64 = 0;
+        for (iface, &(rx, tx)) in interfaces {
+            let Some(&(prev_rx, prev_tx)) = self.net_prev.get(iface) else {
+                continue;
+            };
+            if rx < prev_rx || tx < prev_tx {
+                continue;
+            }
+            let (iface_rx, iface_tx) = (rx - prev_rx, tx - prev_tx);
+            if iface_rx.max(iface_tx) as f64 / elapsed_secs > MAX_PLAUSIBLE_NET_RATE {
+                continue;
+            }
+            rx_delta += iface_rx;
+            tx_delta += iface_tx;
+        }
+
+        if !self.net_prev.is_empty() && elapsed_secs > 0.0 {
+            self.net_rx_rate = rx_delta as f64 / elapsed_secs;
+            self.net_tx_rate = tx_delta as f64 / elapsed_secs;
+        }
+
+        self.net_prev = interfaces.clone();
+    }
+
+    fn push_tx_sample(&mut self, sample: TxSample) {
+        if let Some(prev) = self.tx_samples.back() {
+            // Only add if timestamp is newer
+            if sample.timestamp_ms <= prev.timestamp_ms {
+                return;
+            }
+            // A counter that went backwards (node restart) or jumped implausibly far
+            // would poison the window, so start measuring again from this sample
+            let delta_ms = sample.timestamp_ms - prev.timestamp_ms;
+            let reset = sample.tx_commits < prev.tx_commits;
+            let spike = (sample.tx_commits.saturating_sub(prev.tx_commits) as f64 / delta_ms as f64) * 1000.0
+                > MAX_PLAUSIBLE_TPS;
+            if reset || spike {
+                self.tx_samples.clear();
+            }
+        }
+
+        // Keep one sample at or before the window start as the baseline, so the rate
+        // always covers the full window, however irregular the scrapes
+        let cutoff = sample.timestamp_ms.saturating_sub(TPS_SAMPLE_WINDOW_MS);
+        self.tx_samples.push_back(sample);
+        while self.tx_samples.get(1).is_some_and(|s| s.timestamp_ms <= cutoff) {
+            self.tx_samples.pop_front();
+        }
     }
 
     fn calculate_tps(&mut self) {
@@ -193,29 +743,214 @@ impl AppState {
 
         let tx_delta = newest.tx_commits.saturating_sub(oldest.tx_commits);
         let time_delta_ms = newest.timestamp_ms.saturating_sub(oldest.timestamp_ms);
+        let newest_ms = newest.timestamp_ms;
 
         if time_delta_ms > 0 {
             self.tps_prev = self.tps;
             self.tps = (tx_delta as f64 / time_delta_ms as f64) * 1000.0;
+            self.update_tps_ema();
 
-            // Track peak TPS
+            // Track peak TPS, all-time and over the recent window
             if self.tps > self.tps_peak {
                 self.tps_peak = self.tps;
             }
+            while self.tps_recent_peaks.back().is_some_and(|&(_, tps)| tps <= self.tps) {
+                self.tps_recent_peaks.pop_back();
+            }
+            self.tps_recent_peaks.push_back((newest_ms, self.tps));
+            let cutoff = newest_ms.saturating_sub(TPS_PEAK_WINDOW_MS);
+            while self.tps_recent_peaks.front().is_some_and(|&(ms, _)| ms < cutoff) {
+                self.tps_recent_peaks.pop_front();
+            }
 
             // Add to history for sparkline (capped at reasonable value for display)
             let tps_capped = (self.tps.min(10000.0)) as u64;
-            self.tps_history.push_back(tps_capped);
-            if self.tps_history.len() > TPS_HISTORY_SIZE {
-                self.tps_history.pop_front();
-            }
+            push_capped(&mut self.tps_history, tps_capped, self.tps_window);
+            push_capped(&mut self.tps_history_ms, newest_ms, self.tps_window);
         }
     }
 
+    /// History worth keeping across restarts (see `history::save`)
+    pub fn saved_history(&self) -> SavedHistory {
+        SavedHistory {
+            saved_at: 0,
+            tps_history: self.tps_history.iter().copied().collect(),
+            tps_history_ms: self.tps_history_ms.iter().copied().collect(),
+            tps_peak: self.tps_peak,
+            latency_history: self.latency_history.iter().copied().collect(),
+        }
+    }
+
+    /// Seed the sparklines and peak from a previous session
+    pub fn restore_history(&mut self, saved: SavedHistory) {
+        let window = self.tps_window;
+        let newest = |samples: Vec<u64>| -> VecDeque<u64> {
+            samples[samples.len().saturating_sub(window)..].iter().copied().collect()
+        };
+        // Files from before timestamps were saved can't be labelled, so start the span afresh
+        if saved.tps_history_ms.len() == saved.tps_history.len() {
+            self.tps_history_ms = newest(saved.tps_history_ms);
+        }
+        self.tps_history = newest(saved.tps_history);
+        self.latency_history = newest(saved.latency_history);
+        self.tps_peak = self.tps_peak.max(saved.tps_peak);
+    }
+
+    fn update_tps_ema(&mut self) {
+        let alpha = self.config.tps_smoothing.clamp(0.0, 1.0);
+        // Seed with the first reading so the average doesn't ramp up from zero
+        self.tps_ema = if self.tps_ema == 0.0 {
+            self.tps
+        } else {
+            alpha * self.tps + (1.0 - alpha) * self.tps_ema
+        };
+    }
+
+    /// Re-check alert thresholds, returning alerts that just fired or resolved
+    pub fn update_alerts(&mut self) -> Vec<AlertEvent> {
+        let current = alerts::evaluate(self, &self.config.alerts);
+        self.alerts.update(current)
+    }
+
+    /// Whether to ring the terminal bell for this round of alert `events`: immediately
+    /// when an alert fires, then every `bell_interval_secs` until acknowledged. A block
+    /// stall rings once per episode; the flashing header keeps signalling it after that
+    pub fn bell_due(&mut self, events: &[AlertEvent]) -> bool {
+        let config = &self.config.alerts;
+        let fired = events.iter().any(|e| e.transition == Transition::Fired);
+        // A newly fired alert needs attention even if earlier ones were acknowledged
+        if fired {
+            self.bell_silenced = false;
+        }
+        if !config.bell || self.bell_silenced || self.alerts.active().is_empty() {
+            return false;
+        }
+
+        let repeat = config.bell_interval_secs > 0
+            && self.alerts.active().iter().any(|a| a.kind != AlertKind::StaleBlock)
+            && self
+                .last_bell
+                .is_none_or(|at| at.elapsed() >= Duration::from_secs(config.bell_interval_secs));
+        if fired || repeat {
+            self.last_bell = Some(Instant::now());
+            return true;
+        }
+        false
+    }
+
+    /// Silence the bell until another alert fires
+    pub fn acknowledge_alerts(&mut self) {
+        if !self.alerts.active().is_empty() {
+            self.bell_silenced = true;
+        }
+    }
+
+    /// Whether any of `kinds` is currently firing
+    pub fn alert_active(&self, kinds: &[AlertKind]) -> bool {
+        self.alerts.active().iter().any(|a| kinds.contains(&a.kind))
+    }
+
     pub fn set_error(&mut self, error: String) {
+        // A source failing every refresh would flood the log, so repeats just bump the time
+        match self.error_log.back_mut() {
+            Some((at, last)) if *last == error => *at = Instant::now(),
+            _ => {
+                self.error_log.push_back((Instant::now(), error.clone()));
+                if self.error_log.len() > ERROR_LOG_SIZE {
+                    self.error_log.pop_front();
+                }
+            }
+        }
         self.last_error = Some(error);
     }
 
+    /// Record a failed fetch from `source`
+    pub fn source_failed(&mut self, source: DataSource, error: String) {
+        match source {
+            DataSource::Metrics => self.metrics_ok = Some(false),
+            DataSource::Rpc => self.rpc_ok = Some(false),
+            DataSource::System => self.system_ok = Some(false),
+        }
+        self.set_error(error);
+    }
+
+    /// Whether the latest fetch from `source` succeeded, None before its first report
+    pub fn source_ok(&self, source: DataSource) -> Option<bool> {
+        match source {
+            DataSource::Metrics => self.metrics_ok,
+            DataSource::Rpc => self.rpc_ok,
+            DataSource::System => self.system_ok,
+        }
+    }
+
+    pub fn toggle_error_log(&mut self) {
+        self.show_error_log = !self.show_error_log;
+    }
+
+    pub fn set_notice(&mut self, notice: String) {
+        self.notice = Some((Instant::now(), notice));
+    }
+
+    /// Current notice, if it was set within the last 30 seconds
+    pub fn notice(&self) -> Option<&str> {
+        const NOTICE_DURATION: Duration = Duration::from_secs(30);
+        self.notice
+            .as_ref()
+            .filter(|(at, _)| at.elapsed() < NOTICE_DURATION)
+            .map(|(_, msg)| msg.as_str())
+    }
+
+    /// How long `source` has gone without a successful update, if that is long
+    /// enough (several missed refreshes) that its values shouldn't be trusted
+    pub fn stale_for(&self, source: DataSource) -> Option<Duration> {
+        let updated_at = match source {
+            DataSource::Metrics => self.metrics_updated_at,
+            DataSource::Rpc => self.rpc_updated_at,
+            DataSource::System => self.system_updated_at,
+        };
+        let fetch_age = updated_at
+            .map(|at| at.elapsed())
+            .filter(|&age| age > source.stale_after(self.refresh));
+        // An exporter serving a cached scrape answers on time with old samples
+        let sample_age = (source == DataSource::Metrics).then(|| self.metrics_sample_lag()).flatten();
+        fetch_age.max(sample_age)
+    }
+
+    /// How far the exporter's sample timestamp is behind the wall clock, if by more
+    /// than METRICS_TIMESTAMP_LAG. Not checked when replaying a recording, whose
+    /// timestamps are old by design.
+    pub fn metrics_sample_lag(&self) -> Option<Duration> {
+        let sample_ms = self.metrics.tx_commits_timestamp_ms;
+        if sample_ms == 0 || self.config.replay.is_some() {
+            return None;
+        }
+        let now_ms = SystemTime::now().duration_since(UNIX_EPOCH).ok()?.as_millis() as u64;
+        Some(Duration::from_millis(now_ms.saturating_sub(sample_ms))).filter(|&lag| lag > METRICS_TIMESTAMP_LAG)
+    }
+
+    /// Whether the metrics endpoint keeps returning the same sample (exporter stalled),
+    /// which freezes TPS and block height while the fetches themselves succeed
+    pub fn metrics_frozen(&self) -> bool {
+        self.metrics_frozen_fetches >= METRICS_FROZEN_AFTER
+    }
+
+    /// Whether `source` last succeeded and is neither stale nor (for metrics) frozen
+    pub fn source_healthy(&self, source: DataSource) -> bool {
+        self.source_ok(source) == Some(true)
+            && self.stale_for(source).is_none()
+            && !(source == DataSource::Metrics && self.metrics_frozen())
+    }
+
+    /// Time since the reference RPC last answered, None if it never has
+    pub fn external_block_age(&self) -> Option<Duration> {
+        self.external_block_at.map(|at| at.elapsed())
+    }
+
+    /// Whether the cached reference block is too old to judge sync by
+    pub fn external_block_stale(&self) -> bool {
+        self.external_block_age().is_some_and(|age| age > EXTERNAL_BLOCK_STALE_AFTER)
+    }
+
     pub fn time_since_last_block(&self) -> Option<Duration> {
         self.last_block_time.map(|t| t.elapsed())
     }
@@ -233,10 +968,58 @@ impl AppState {
         &self.rpc_data.recent_blocks
     }
 
+    /// Mean seconds between consecutive recent blocks. Timestamps are whole seconds, so
+    /// only the average over many blocks resolves sub-second block times. Pairs with a
+    /// missing or out-of-order timestamp are skipped.
+    pub fn avg_block_time(&self) -> Option<f64> {
+        let deltas: Vec<u64> = self
+            .recent_blocks()
+            .windows(2)
+            .filter(|pair| pair[1].timestamp > 0 && pair[0].timestamp >= pair[1].timestamp)
+            .map(|pair| pair[0].timestamp - pair[1].timestamp)
+            .collect();
+        (!deltas.is_empty()).then(|| deltas.iter().sum::<u64>() as f64 / deltas.len() as f64)
+    }
+
     pub fn tps_sparkline_data(&self) -> Vec<u64> {
         self.tps_history.iter().copied().collect()
     }
 
+    /// Data for the graph panel, based on the selected metric
+    pub fn graph_data(&self) -> Vec<u64> {
+        match self.graph_metric {
+            GraphMetric::Tps => self.tps_sparkline_data(),
+            GraphMetric::Memory => self.mem_history.iter().copied().collect(),
+            GraphMetric::Cpu => self.cpu_history.iter().copied().collect(),
+            GraphMetric::Latency if self.config.graph.latency_log_scale => self
+                .latency_history
+                .iter()
+                // Scale up so the sparkline keeps some resolution after ln()
+                .map(|&ms| ((ms as f64).ln_1p() * 100.0) as u64)
+                .collect(),
+            GraphMetric::Latency => self.latency_history.iter().copied().collect(),
+            // The UI draws TX next to this from net_tx_history
+            GraphMetric::Network => self.net_rx_history.iter().copied().collect(),
+            // Oldest block first, like the time-based histories
+            GraphMetric::Gas => self
+                .recent_blocks()
+                .iter()
+                .rev()
+                .filter(|b| b.gas_limit > 0)
+                .map(|b| (b.gas_used as f64 / b.gas_limit as f64 * 100.0).round() as u64)
+                .collect(),
+            GraphMetric::Pool => self.pending_history.iter().copied().collect(),
+        }
+    }
+
+    /// Fixed y-scale for percentage metrics, None to auto-scale
+    pub fn graph_max(&self) -> Option<u64> {
+        match self.graph_metric {
+            GraphMetric::Tps | GraphMetric::Latency | GraphMetric::Network | GraphMetric::Pool => None,
+            GraphMetric::Memory | GraphMetric::Cpu | GraphMetric::Gas => Some(100),
+        }
+    }
+
     pub fn sync_status(&self) -> &'static str {
         if self.metrics.is_synced() {
             "synced"
@@ -267,6 +1050,29 @@ impl AppState {
         }
     }
 
+    /// Time covered by the TPS graph, from the metrics timestamps of its samples
+    /// (the sample count alone assumes exactly one scrape per second)
+    pub fn tps_window_span(&self) -> Option<Duration> {
+        let (first, last) = (self.tps_history_ms.front()?, self.tps_history_ms.back()?);
+        (last > first).then(|| Duration::from_millis(last - first))
+    }
+
+    /// Highest TPS over the last 10 minutes of readings
+    pub fn tps_recent_peak(&self) -> f64 {
+        self.tps_recent_peaks.front().map_or(0.0, |&(_, tps)| tps)
+    }
+
+    /// Mean TPS over the sparkline window, None until the first reading
+    pub fn tps_avg(&self) -> Option<f64> {
+        (!self.tps_history.is_empty())
+            .then(|| self.tps_history.iter().sum::<u64>() as f64 / self.tps_history.len() as f64)
+    }
+
+    /// Lowest TPS over the sparkline window, None until the first reading
+    pub fn tps_min(&self) -> Option<u64> {
+        self.tps_history.iter().copied().min()
+    }
+
     /// Returns TPS trend: 1 = up, -1 = down, 0 = stable
     pub fn tps_trend(&self) -> i8 {
         let threshold = 50.0; // Need 50 TPS difference to show trend
@@ -305,16 +1111,586 @@ impl AppState {
         }
     }
 
-    /// Format bytes per second as human readable
+    /// Returns upstream validator trend: 1 = up, -1 = down, 0 = stable
+    pub fn validators_trend(&self) -> i8 {
+        let current = self.metrics.upstream_validators;
+        if current > self.validators_prev {
+            1
+        } else if current < self.validators_prev {
+            -1
+        } else {
+            0
+        }
+    }
+
+    /// Returns pending pool trend: 1 = growing, -1 = draining, 0 = stable
+    pub fn pending_trend(&self) -> i8 {
+        let current = self.metrics.pending_txs;
+        let threshold = 100; // Need 100 tx difference to show trend
+        if current > self.pending_prev + threshold {
+            1
+        } else if current + threshold < self.pending_prev {
+            -1
+        } else {
+            0
+        }
+    }
+
+    pub fn snapshot(&self) -> Snapshot {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        let sys = &self.system;
+        // monad-mpt derived values are omitted rather than reported as zero
+        let mpt = sys.disk_data_available;
+
+        Snapshot {
+            timestamp,
+            node_id: sys.node_id.clone(),
+            block_height: self.block_height(),
+            block_age_secs: self.time_since_last_block().map(|d| d.as_secs_f64()),
+            external_block: sys.external_block,
+            external_block_age_secs: self.external_block_age().map(|d| d.as_secs_f64()),
+            block_difference: sys.block_difference(self.block_height()),
+            sync_status: self.sync_status(),
+            sync_pct: self.metrics.sync_percentage(),
+            tps: self.tps,
+            tps_ema: self.tps_ema,
+            tps_peak: self.tps_peak,
+            tps_peak_recent: self.tps_recent_peak(),
+            tps_trend: self.tps_trend(),
+            peers: self.metrics.peer_count,
+            peers_trend: self.peers_trend(),
+            latency_p99_ms: self.metrics.latency_p99_ms,
+            latency_trend: self.latency_trend(),
+            pending_txs: self.metrics.pending_txs,
+            pending_trend: self.pending_trend(),
+            upstream_validators: self.metrics.upstream_validators,
+            validators_trend: self.validators_trend(),
+            consensus_round: self.metrics.consensus_round,
+            consensus_view: self.metrics.consensus_view,
+            gas_price_gwei: self.rpc_data.gas_price_gwei,
+            cpu_pct: sys.cpu_usage_pct,
+            load_1: sys.load_1,
+            load_5: sys.load_5,
+            load_15: sys.load_15,
+            mem_pct: sys.memory_used_pct,
+            mem_used_gb: sys.memory_used_gb,
+            swap_pct: sys.swap_used_pct,
+            disk_pct: mpt.then_some(sys.disk_used_pct),
+            disk_read_rate: sys.disk_read_rate,
+            disk_write_rate: sys.disk_write_rate,
+            net_rx_rate: self.net_rx_rate,
+            net_tx_rate: self.net_tx_rate,
+            finalized_lag: mpt.then_some(sys.finalized_lag()),
+            verified_gap: mpt.then_some(sys.verified_finalized_gap()),
+            history_count: mpt.then_some(sys.history_count),
+            services: sys
+                .services
+                .iter()
+                .map(|(name, active)| ServiceStatus { name: name.clone(), active: *active })
+                .collect(),
+            sources: DataSource::ALL
+                .iter()
+                .map(|&source| SourceStatus { name: source.name(), healthy: self.source_healthy(source) })
+                .collect(),
+            alerts: self.alerts.active().iter().map(|a| a.message.clone()).collect(),
+            last_error: self.last_error.clone(),
+        }
+    }
+
+    /// Format bytes per second as human readable, in decimal (SI) units like
+    /// network tools use; memory is shown in 1024-based Gi instead
     pub fn format_bandwidth(bytes_per_sec: f64) -> String {
         if bytes_per_sec >= 1_000_000_000.0 {
             format!("{:.1}GB/s", bytes_per_sec / 1_000_000_000.0)
         } else if bytes_per_sec >= 1_000_000.0 {
             format!("{:.1}MB/s", bytes_per_sec / 1_000_000.0)
         } else if bytes_per_sec >= 1_000.0 {
-            format!("{:.0}KB/s", bytes_per_sec / 1_000.0)
+            format!("{:.0}kB/s", bytes_per_sec / 1_000.0)
         } else {
             format!("{:.0}B/s", bytes_per_sec)
         }
     }
 }
+
+fn push_capped(history: &mut VecDeque<u64>, value: u64, cap: usize) {
+    history.push_back(value);
+    if history.len() > cap {
+        history.pop_front();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn interfaces(rx: u64, tx: u64) -> HashMap<String, (u64, u64)> {
+        HashMap::from([("eth0".to_string(), (rx, tx))])
+    }
+
+    #[test]
+    fn test_net_rate_resets_to_zero_on_counter_reset() {
+        let mut state = AppState::new(Config::default());
+
+        state.update_net_rates(&interfaces(1_000_000, 500_000), 5.0);
+        state.update_net_rates(&interfaces(6_000_000, 1_000_000), 5.0);
+        assert_eq!(state.net_rx_rate, 1_000_000.0);
+        assert_eq!(state.net_tx_rate, 100_000.0);
+
+        // Interface restarted: counters drop, rate must not stay frozen at the old value
+        state.update_net_rates(&interfaces(1_000, 1_000), 5.0);
+        assert_eq!(state.net_rx_rate, 0.0);
+        assert_eq!(state.net_tx_rate, 0.0);
+
+        // Next sample measures from the new baseline
+        state.update_net_rates(&interfaces(5_001_000, 1_000), 5.0);
+        assert_eq!(state.net_rx_rate, 1_000_000.0);
+    }
+
+    #[test]
+    fn test_bell_silenced_until_next_alert_fires() {
+        let mut state = AppState::new(Config::default());
+        let alert = |kind| alerts::Alert { kind, message: String::new(), value: 0.0, threshold: 0.0 };
+
+        let events = state.alerts.update(vec![alert(AlertKind::LowPeers)]);
+        assert!(state.bell_due(&events));
+        // Still firing, but within the repeat interval
+        let events = state.alerts.update(vec![alert(AlertKind::LowPeers)]);
+        assert!(!state.bell_due(&events));
+
+        state.acknowledge_alerts();
+        state.last_bell = None;
+        let events = state.alerts.update(vec![alert(AlertKind::LowPeers)]);
+        assert!(!state.bell_due(&events));
+
+        // A different alert firing re-arms the bell
+        let events = state.alerts.update(vec![alert(AlertKind::LowPeers), alert(AlertKind::DiskFull)]);
+        assert!(state.bell_due(&events));
+        assert!(!state.bell_silenced);
+    }
+
+    #[test]
+    fn test_bell_rings_once_per_stall() {
+        let mut state = AppState::new(Config::default());
+        let stall = || vec![alerts::Alert { kind: AlertKind::StaleBlock, message: String::new(), value: 0.0, threshold: 0.0 }];
+
+        let events = state.alerts.update(stall());
+        assert!(state.bell_due(&events));
+        // Past the repeat interval the stall stays quiet
+        state.last_bell = Some(Instant::now() - Duration::from_secs(120));
+        let events = state.alerts.update(stall());
+        assert!(!state.bell_due(&events));
+
+        // A block arrives, then the chain stalls again
+        let events = state.alerts.update(Vec::new());
+        assert!(!state.bell_due(&events));
+        let events = state.alerts.update(stall());
+        assert!(state.bell_due(&events));
+    }
+
+    #[test]
+    fn test_source_stale_after_missed_refreshes() {
+        let mut state = AppState::new(Config::default());
+        // Never updated: nothing to distrust yet
+        assert!(state.stale_for(DataSource::Metrics).is_none());
+
+        state.metrics_updated_at = Some(Instant::now() - Duration::from_secs(2));
+        state.system_updated_at = Some(Instant::now() - Duration::from_secs(2));
+        assert!(state.stale_for(DataSource::Metrics).is_none());
+        assert!(state.stale_for(DataSource::System).is_none());
+
+        state.metrics_updated_at = Some(Instant::now() - Duration::from_secs(12));
+        assert!(state.stale_for(DataSource::Metrics).is_some_and(|age| age.as_secs() >= 12));
+    }
+
+    #[test]
+    fn test_refresh_intervals_scaled() {
+        let intervals = RefreshIntervals::from_config(&Config::default());
+        let faster = intervals.scaled(true);
+        assert_eq!((faster.metrics.as_millis(), faster.system.as_millis()), (500, 2_500));
+        // Each interval stops at the floor on its own
+        let fastest = faster.scaled(true).scaled(true);
+        assert_eq!((fastest.metrics.as_millis(), fastest.system.as_millis()), (250, 625));
+        assert_eq!(intervals.scaled(false).system.as_secs(), 10);
+
+        // Out-of-range values from the config file are clamped rather than used as is
+        let config = Config { metrics_interval_ms: 0, system_interval_ms: u64::MAX, ..Default::default() };
+        let intervals = RefreshIntervals::from_config(&config);
+        assert_eq!(intervals.metrics.as_millis(), MIN_REFRESH_INTERVAL_MS as u128);
+        assert_eq!(intervals.system, MAX_REFRESH_INTERVAL);
+    }
+
+    #[test]
+    fn test_metrics_sample_lag() {
+        let now_ms = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_millis() as u64;
+        let mut state = AppState::new(Config::default());
+        state.metrics_updated_at = Some(Instant::now());
+
+        state.metrics.tx_commits_timestamp_ms = now_ms - 2_000;
+        assert!(state.stale_for(DataSource::Metrics).is_none());
+
+        // Fetched just now, but the sample itself is a minute old
+        state.metrics.tx_commits_timestamp_ms = now_ms - 60_000;
+        assert!(state.stale_for(DataSource::Metrics).is_some_and(|age| age.as_secs() >= 60));
+        assert!(!state.source_healthy(DataSource::Metrics));
+
+        state.config.replay = Some("/tmp/recording".to_string());
+        assert!(state.metrics_sample_lag().is_none());
+    }
+
+    #[test]
+    fn test_source_healthy() {
+        let mut state = AppState::new(Config::default());
+        assert!(!state.source_healthy(DataSource::Rpc));
+
+        state.rpc_ok = Some(true);
+        state.rpc_updated_at = Some(Instant::now());
+        assert!(state.source_healthy(DataSource::Rpc));
+
+        state.metrics_ok = Some(true);
+        state.metrics_updated_at = Some(Instant::now());
+        state.metrics_frozen_fetches = METRICS_FROZEN_AFTER;
+        assert!(!state.source_healthy(DataSource::Metrics));
+        assert_eq!(state.snapshot().sources.len(), 3);
+    }
+
+    #[test]
+    fn test_external_block_kept_across_failed_fetch() {
+        let mut state = AppState::new(Config::default());
+
+        state.update_system(SystemData { external_block: 1_000, ..Default::default() });
+        assert_eq!(state.system.external_block, 1_000);
+
+        // Reference RPC failed this tick
+        let failed = || SystemData { external_error: Some("timed out".to_string()), ..Default::default() };
+        state.update_system(failed());
+        assert_eq!(state.system.external_block, 1_000);
+        assert!(!state.external_block_stale());
+        assert!(state.notice().is_some_and(|n| n.contains("rpc-mainnet.monadinfra.com unreachable: timed out")));
+
+        // Reported once per outage
+        state.notice = None;
+        state.update_system(failed());
+        assert!(state.notice().is_none());
+
+        state.external_block_at = Some(Instant::now() - Duration::from_secs(31));
+        assert!(state.external_block_stale());
+    }
+
+    #[test]
+    fn test_format_bandwidth_uses_decimal_units() {
+        assert_eq!(AppState::format_bandwidth(999.0), "999B/s");
+        assert_eq!(AppState::format_bandwidth(1_000.0), "1kB/s");
+        assert_eq!(AppState::format_bandwidth(1_023.0), "1kB/s");
+        assert_eq!(AppState::format_bandwidth(1_024.0), "1kB/s");
+        assert_eq!(AppState::format_bandwidth(1_000_000.0), "1.0MB/s");
+        assert_eq!(AppState::format_bandwidth(1_500_000_000.0), "1.5GB/s");
+    }
+
+    fn tx_sample(tx_commits: u64, timestamp_ms: u64) -> TxSample {
+        TxSample { tx_commits, timestamp_ms }
+    }
+
+    #[test]
+    fn test_tps_restarts_after_counter_reset() {
+        let mut state = AppState::new(Config::default());
+
+        state.push_tx_sample(tx_sample(10_000, 1_000));
+        state.push_tx_sample(tx_sample(11_000, 2_000));
+        state.calculate_tps();
+        assert_eq!(state.tps, 1_000.0);
+
+        // Node restarted: the counter drops to near zero and the old window is discarded
+        state.push_tx_sample(tx_sample(50, 3_000));
+        assert_eq!(state.tx_samples.len(), 1);
+
+        state.push_tx_sample(tx_sample(550, 4_000));
+        state.calculate_tps();
+        assert_eq!(state.tps, 500.0);
+
+        // A single absurd jump re-baselines instead of producing a huge TPS
+        state.push_tx_sample(tx_sample(u64::MAX / 2, 5_000));
+        state.calculate_tps();
+        assert_eq!(state.tx_samples.len(), 1);
+        assert_eq!(state.tps, 500.0);
+    }
+
+    #[test]
+    fn test_tps_over_time_window() {
+        let mut state = AppState::new(Config::default());
+
+        // Scrapes every second at 100 tx/s: the window settles at 10s (11 samples)
+        for i in 0..30 {
+            state.push_tx_sample(tx_sample(i * 100, i * 1_000));
+        }
+        assert_eq!(state.tx_samples.len(), 11);
+        state.calculate_tps();
+        assert_eq!(state.tps, 100.0);
+
+        // A 25s scrape gap keeps the previous sample as the baseline
+        state.push_tx_sample(tx_sample(29 * 100 + 5_000, 54_000));
+        assert_eq!(state.tx_samples.len(), 2);
+        state.calculate_tps();
+        assert_eq!(state.tps, 200.0);
+    }
+
+    #[test]
+    fn test_metrics_frozen() {
+        let mut state = AppState::new(Config::default());
+        let metrics = |timestamp_ms| PrometheusMetrics { tx_commits_timestamp_ms: timestamp_ms, ..Default::default() };
+
+        state.update_metrics(metrics(1_000));
+        for _ in 0..METRICS_FROZEN_AFTER {
+            assert!(!state.metrics_frozen());
+            state.update_metrics(metrics(1_000));
+        }
+        assert!(state.metrics_frozen());
+
+        state.update_metrics(metrics(2_000));
+        assert!(!state.metrics_frozen());
+    }
+
+    #[test]
+    fn test_tps_ema_smooths_readings() {
+        let mut state = AppState::new(Config { tps_smoothing: 0.5, ..Config::default() });
+
+        state.tps = 1_000.0;
+        state.update_tps_ema();
+        assert_eq!(state.tps_ema, 1_000.0);
+
+        state.tps = 2_000.0;
+        state.update_tps_ema();
+        assert_eq!(state.tps_ema, 1_500.0);
+    }
+
+    #[test]
+    fn test_error_log_collapses_repeats_and_caps() {
+        let mut state = AppState::new(Config::default());
+
+        state.set_error("metrics: timeout".to_string());
+        state.set_error("metrics: timeout".to_string());
+        state.set_error("rpc: closed".to_string());
+        assert_eq!(state.error_log.len(), 2);
+        assert_eq!(state.last_error.as_deref(), Some("rpc: closed"));
+
+        for i in 0..ERROR_LOG_SIZE {
+            state.set_error(format!("system: {}", i));
+        }
+        assert_eq!(state.error_log.len(), ERROR_LOG_SIZE);
+        assert_eq!(state.error_log.front().unwrap().1, "system: 0");
+    }
+
+    #[test]
+    fn test_click_hit_areas() {
+        let mut state = AppState::new(Config::default());
+        state.rpc_data.recent_blocks = (0..3)
+            .map(|i| Block { number: 100 - i, hash: String::new(), tx_count: 0, timestamp: 0, gas_used: 0, gas_limit: 0 })
+            .collect();
+        state.hit_areas = HitAreas {
+            theme_label: Rect::new(50, 30, 6, 1),
+            block_rows: Rect::new(2, 20, 80, 3),
+        };
+
+        state.click(52, 30);
+        assert_eq!(state.theme, Theme::Light);
+
+        state.click(10, 21);
+        assert_eq!(state.selected_block, Some(99));
+        assert!(!state.show_block_detail);
+        state.click(10, 21);
+        assert!(state.show_block_detail);
+
+        // Outside any area
+        state.click(10, 25);
+        assert_eq!(state.selected_block, Some(99));
+    }
+
+    #[test]
+    fn test_selection_follows_block_as_new_ones_arrive() {
+        let mut state = AppState::new(Config::default());
+        let blocks = |newest: u64| RpcData {
+            block_number: newest,
+            recent_blocks: (0..3)
+                .map(|i| Block { number: newest - i, hash: format!("0x{:x}", newest - i), tx_count: 0, timestamp: 0, gas_used: 0, gas_limit: 0 })
+                .collect(),
+            ..Default::default()
+        };
+        state.update_rpc(blocks(100));
+        state.select_next_block();
+        state.select_next_block();
+        assert_eq!(state.selected_block().map(|b| b.number), Some(99));
+
+        state.update_rpc(blocks(101));
+        assert_eq!(state.selected_block().map(|b| b.number), Some(99));
+        // `y` copies the selected block's hash, not the one now in its old row
+        assert_eq!(state.selected_hash(), Some("0x63"));
+        state.select_prev_block();
+        assert_eq!(state.selected_block().map(|b| b.number), Some(100));
+
+        // Pushed out of the table
+        state.update_rpc(blocks(105));
+        assert!(state.selected_block().is_none());
+    }
+
+    #[test]
+    fn test_remote_node() {
+        let node = NodeConfig {
+            name: "validator-2".to_string(),
+            rpc_url: "ws://10.0.0.2:8081".to_string(),
+            metrics_url: "http://10.0.0.2:8889/metrics".to_string(),
+        };
+        let mut remote = AppState::remote(&Config::default(), &node);
+        assert_eq!(remote.config.rpc_url, "ws://10.0.0.2:8081");
+        assert_eq!(remote.system.node_id, "validator-2");
+
+        remote.update_reference_block(1_000);
+        remote.update_reference_block(0);
+        assert_eq!(remote.system.block_difference(990), 10);
+
+        let mut local = AppState::new(Config::default());
+        local.toggle_theme();
+        local.cycle_graph();
+        remote.take_view_from(&local);
+        assert_eq!(remote.theme, local.theme);
+        assert_eq!(remote.graph_metric, local.graph_metric);
+    }
+
+    #[test]
+    fn test_tps_window() {
+        let mut state = AppState::new(Config { tps_window: 3, ..Config::default() });
+        assert_eq!(state.tps_window_span(), None);
+
+        // Scrapes 2s apart: 4 samples give 3 readings, all kept
+        for i in 0..4 {
+            state.push_tx_sample(tx_sample(i * 100, 1_000 + i * 2_000));
+            state.calculate_tps();
+        }
+        assert_eq!(state.tps_history.len(), 3);
+        assert_eq!(state.tps_window_span(), Some(Duration::from_secs(4)));
+
+        // A fourth reading pushes the oldest out
+        state.push_tx_sample(tx_sample(400, 9_000));
+        state.calculate_tps();
+        assert_eq!(state.tps_history.len(), 3);
+        assert_eq!(state.tps_window_span(), Some(Duration::from_secs(4)));
+    }
+
+    #[test]
+    fn test_tps_avg_and_min() {
+        let mut state = AppState::new(Config::default());
+        assert_eq!(state.tps_avg(), None);
+        assert_eq!(state.tps_min(), None);
+
+        state.tps_history.extend([300, 100, 200]);
+        assert_eq!(state.tps_avg(), Some(200.0));
+        assert_eq!(state.tps_min(), Some(100));
+    }
+
+    #[test]
+    fn test_recent_peak_decays() {
+        let mut state = AppState::new(Config::default());
+        let mut commits = 0;
+        let mut scrape = |state: &mut AppState, ms: u64, tps: u64| {
+            commits += tps;
+            state.update_metrics(PrometheusMetrics {
+                tx_commits: commits,
+                tx_commits_timestamp_ms: ms,
+                ..Default::default()
+            });
+        };
+
+        // One second at 5000 TPS among readings of 100
+        scrape(&mut state, 1_000, 0);
+        scrape(&mut state, 2_000, 5_000);
+        for s in 3..=60 {
+            scrape(&mut state, s * 1_000, 100);
+        }
+        assert!(state.tps_recent_peak() > 400.0);
+
+        // Once the spike is more than 10 minutes old, the recent peak is back to normal
+        for s in 61..=700 {
+            scrape(&mut state, s * 1_000, 100);
+        }
+        assert!((state.tps_recent_peak() - 100.0).abs() < 1.0, "{}", state.tps_recent_peak());
+        assert!(state.tps_peak > 400.0);
+    }
+
+    #[test]
+    fn test_restore_history() {
+        let mut state = AppState::new(Config::default());
+        state.restore_history(SavedHistory {
+            tps_history: (0..310).collect(),
+            tps_peak: 900.0,
+            latency_history: vec![5],
+            ..Default::default()
+        });
+        assert_eq!(state.tps_history.len(), 300);
+        assert_eq!(state.tps_history.front(), Some(&10));
+        assert_eq!(state.tps_peak, 900.0);
+        assert_eq!(state.saved_history().latency_history, vec![5]);
+    }
+
+    #[test]
+    fn test_avg_block_time() {
+        let mut state = AppState::new(Config::default());
+        assert_eq!(state.avg_block_time(), None);
+
+        // Newest first; the 0 timestamp and the out-of-order pair are ignored
+        let block = |timestamp| Block {
+            number: 0,
+            hash: String::new(),
+            tx_count: 0,
+            timestamp,
+            gas_used: 0,
+            gas_limit: 0,
+        };
+        state.rpc_data.recent_blocks = vec![block(102), block(101), block(101), block(100), block(0), block(90), block(95)];
+        assert_eq!(state.avg_block_time(), Some(2.0 / 3.0));
+    }
+
+    #[test]
+    fn test_request_quit() {
+        let mut state = AppState::new(Config::default());
+        assert!(state.request_quit());
+
+        let mut state = AppState::new(Config { confirm_quit: true, ..Config::default() });
+        assert!(!state.request_quit());
+        assert!(state.confirming_quit);
+        assert!(state.request_quit());
+    }
+
+    #[test]
+    fn test_theme_from_name() {
+        assert_eq!(Theme::from_name("ocean"), Some(Theme::Ocean));
+        assert_eq!(Theme::from_name("High-Contrast"), Some(Theme::HighContrast));
+        assert_eq!(Theme::from_name("neon"), None);
+
+        let config = Config { theme: Some("dracula".to_string()), ..Config::default() };
+        assert_eq!(AppState::new(config).theme, Theme::Dracula);
+    }
+
+    #[test]
+    fn test_select_theme_by_index() {
+        let mut state = AppState::new(Config::default());
+
+        state.select_theme(6);
+        assert_eq!(state.theme, Theme::Dracula);
+
+        // No palette configured: the custom slot and out-of-range keys do nothing
+        state.select_theme(9);
+        assert_eq!(state.theme, Theme::Dracula);
+        state.select_theme(42);
+        assert_eq!(state.theme, Theme::Dracula);
+    }
+
+    #[test]
+    fn test_net_rate_ignores_implausible_spike() {
+        let mut state = AppState::new(Config::default());
+
+        state.update_net_rates(&interfaces(0, 0), 5.0);
+        state.update_net_rates(&interfaces(u64::MAX / 2, 0), 5.0);
+        assert_eq!(state.net_rx_rate, 0.0);
+    }
+}
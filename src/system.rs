@@ -1,46 +1,78 @@
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use futures::{SinkExt, StreamExt};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
 use serde_json::json;
+use std::collections::HashMap;
 use std::fs;
 use std::process::Command;
+use std::time::Duration;
+use sysinfo::{Networks, System};
 use tokio_tungstenite::{connect_async, tungstenite::Message};
 
+use crate::rpc::ws_request;
+
 /// Data from system commands (monad-mpt, systemctl, external RPC)
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct SystemData {
-    // Disk info from monad-mpt
+    // Disk info from monad-mpt (only meaningful when disk_data_available)
+    pub disk_data_available: bool,
     pub disk_capacity_gb: f64,
     pub disk_used_gb: f64,
     pub disk_used_pct: f64,
+
+    // Triedb device throughput from /proc/diskstats (bytes per second)
+    pub disk_read_rate: f64,
+    pub disk_write_rate: f64,
+
     pub history_count: u64,
     pub history_earliest: u64,
     pub history_latest: u64,
     pub latest_finalized: u64,
     pub latest_verified: u64,
 
-    // Services status
-    pub service_bft: bool,
-    pub service_execution: bool,
-    pub service_rpc: bool,
+    // Services status (unit name, active); only meaningful when services_available
+    pub services_available: bool,
+    pub services: Vec<(String, bool)>,
 
     // External block for comparison
     pub external_block: u64,
+    // Why the reference RPC couldn't be read this time (external_block is then 0)
+    #[serde(default)]
+    pub external_error: Option<String>,
 
     // System resources
     pub memory_used_pct: f64,
     pub memory_used_gb: f64,
     pub memory_total_gb: f64,
+    pub swap_total_gb: f64,
+    pub swap_used_gb: f64,
+    pub swap_used_pct: f64,
+    // CPU usage over the last interval; false on the first sample, which has no baseline
+    pub cpu_measured: bool,
     pub cpu_usage_pct: f64,
+    pub cpu_cores_pct: Vec<f64>,
+    pub cpu_count: usize,
+
+    // 1/5/15-minute load average from /proc/loadavg
+    pub load_1: f64,
+    pub load_5: f64,
+    pub load_15: f64,
 
-    // Network (bytes since boot, for calculating rate)
-    pub net_rx_bytes: u64,
-    pub net_tx_bytes: u64,
+    // Resident memory of the primary service's main process (0 if not running)
+    pub process_rss_gb: f64,
+
+    // Network (bytes since boot per interface as (rx, tx), for calculating rate)
+    pub net_interfaces: HashMap<String, (u64, u64)>,
 
     // Node identifier (hostname)
     pub node_id: String,
 
     // Service start time (seconds since epoch)
     pub service_started_at: u64,
+
+    // One-off message for the operator (e.g. a missing tool)
+    pub notice: Option<String>,
 }
 
 impl SystemData {
@@ -56,8 +88,12 @@ impl SystemData {
         self.history_latest.saturating_sub(self.latest_finalized)
     }
 
+    pub fn verified_finalized_gap(&self) -> u64 {
+        self.latest_verified.saturating_sub(self.latest_finalized)
+    }
+
     pub fn all_services_running(&self) -> bool {
-        self.service_bft && self.service_execution && self.service_rpc
+        self.services.iter().all(|(_, active)| *active)
     }
 
     /// Returns formatted uptime since service restart
@@ -91,65 +127,193 @@ impl SystemData {
 }
 
 pub struct SystemClient {
-    network: String,
+    // Reference RPC for the block difference
+    external_rpc_url: String,
+    // Authorization header value sent with external RPC requests
+    auth_header: Option<String>,
+    // Limit for the whole external RPC round trip
+    timeout: Duration,
+    http: Client,
+    services: Vec<String>,
+    storage_path: String,
+    mpt_missing_reported: bool,
+    systemctl_missing_reported: bool,
+    // sysinfo handles keep the previous sample, so CPU usage is over the last interval
+    sys: System,
+    networks: Networks,
+    cpu_baseline_taken: bool,
+    // Previous (sectors_read, sectors_written, taken_at) for the triedb device
+    disk_io_prev: Option<(u64, u64, std::time::Instant)>,
 }
 
 impl SystemClient {
-    pub fn new(network: &str) -> Self {
+    pub fn new(
+        services: Vec<String>,
+        storage_path: &str,
+        external_rpc_url: String,
+        auth_header: Option<String>,
+        timeout: Duration,
+    ) -> Self {
         Self {
-            network: network.to_string(),
+            external_rpc_url,
+            auth_header,
+            timeout,
+            http: Client::builder().timeout(timeout).build().unwrap_or_default(),
+            services,
+            storage_path: storage_path.to_string(),
+            mpt_missing_reported: false,
+            systemctl_missing_reported: false,
+            sys: System::new(),
+            networks: Networks::new_with_refreshed_list(),
+            cpu_baseline_taken: false,
+            disk_io_prev: None,
         }
     }
 
-    pub async fn fetch(&self) -> Result<SystemData> {
+    pub async fn fetch(&mut self) -> Result<SystemData> {
         let mut data = SystemData::default();
 
         // Fetch monad-mpt data (blocking, but fast)
-        if let Ok(mpt_output) = tokio::task::spawn_blocking(|| {
+        let storage_path = self.storage_path.clone();
+        match tokio::task::spawn_blocking(move || {
             Command::new("monad-mpt")
-                .args(["--storage", "/dev/triedb"])
+                .args(["--storage", &storage_path])
                 .output()
         })
         .await?
         {
-            let output = String::from_utf8_lossy(&mpt_output.stdout);
-            parse_mpt_output(&output, &mut data);
+            Ok(mpt_output) if mpt_output.status.success() => {
+                let output = String::from_utf8_lossy(&mpt_output.stdout);
+                parse_mpt_output(&output, &mut data);
+                // A zero-capacity result means the disk line wasn't there to parse
+                data.disk_data_available = data.disk_capacity_gb > 0.0;
+            }
+            Ok(_) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                // Report once rather than on every tick
+                if !self.mpt_missing_reported {
+                    self.mpt_missing_reported = true;
+                    data.notice = Some("monad-mpt not found on PATH, disk stats unavailable".to_string());
+                }
+            }
+            Err(_) => {}
         }
 
         // Fetch services status (blocking, but fast)
-        if let Ok(services) = tokio::task::spawn_blocking(fetch_services_status).await {
-            data.service_bft = services.0;
-            data.service_execution = services.1;
-            data.service_rpc = services.2;
-            data.service_started_at = services.3;
+        let units = self.services.clone();
+        match tokio::task::spawn_blocking(move || fetch_services_status(&units)).await {
+            Ok(Some((services, started_at))) => {
+                data.services_available = true;
+                data.services = services;
+                data.service_started_at = started_at;
+            }
+            Ok(None) => {
+                // No systemctl (e.g. running in a container)
+                if !self.systemctl_missing_reported {
+                    self.systemctl_missing_reported = true;
+                    let message = "systemctl not found on PATH, service status unavailable";
+                    data.notice = Some(match data.notice.take() {
+                        Some(notice) => format!("{}; {}", notice, message),
+                        None => message.to_string(),
+                    });
+                }
+            }
+            Err(_) => {}
         }
 
-        // Fetch external block number
-        if let Ok(block) = self.fetch_external_block().await {
-            data.external_block = block;
+        // Fetch RSS of the primary service's main process (blocking, but fast)
+        if let Some(unit) = self.services.first().cloned() {
+            if let Ok(Some(rss_kb)) = tokio::task::spawn_blocking(move || fetch_process_rss_kb(&unit)).await {
+                data.process_rss_gb = rss_kb as f64 / 1024.0 / 1024.0;
+            }
         }
 
-        // Fetch system resources (blocking, but fast)
-        if let Ok(resources) = tokio::task::spawn_blocking(fetch_system_resources).await {
-            data.memory_used_pct = resources.0;
-            data.memory_used_gb = resources.1;
-            data.memory_total_gb = resources.2;
-            data.cpu_usage_pct = resources.3;
-            data.net_rx_bytes = resources.4;
-            data.net_tx_bytes = resources.5;
+        // Fetch external block number; on failure it stays 0 and the reason is kept
+        match tokio::time::timeout(self.timeout, self.fetch_external_block()).await {
+            Ok(Ok(block)) => data.external_block = block,
+            Ok(Err(e)) => data.external_error = Some(format!("{:#}", e)),
+            Err(_) => data.external_error = Some("timed out".to_string()),
+        }
+
+        // Fetch memory, swap and network counters via sysinfo (fast)
+        self.refresh_resources(&mut data);
+
+        // Disk throughput from sector deltas since the previous fetch
+        let storage_path = self.storage_path.clone();
+        if let Ok(Some((read, written))) = tokio::task::spawn_blocking(move || read_disk_sectors(&storage_path)).await {
+            let now = std::time::Instant::now();
+            if let Some((prev_read, prev_written, prev_at)) = self.disk_io_prev {
+                let secs = now.duration_since(prev_at).as_secs_f64();
+                if secs > 0.0 {
+                    data.disk_read_rate = read.saturating_sub(prev_read) as f64 * SECTOR_SIZE / secs;
+                    data.disk_write_rate = written.saturating_sub(prev_written) as f64 * SECTOR_SIZE / secs;
+                }
+            }
+            self.disk_io_prev = Some((read, written, now));
         }
 
         // Fetch hostname
         if let Ok(hostname) = fs::read_to_string("/etc/hostname") {
             data.node_id = hostname.trim().to_string();
+        } else if let Some(hostname) = System::host_name() {
+            data.node_id = hostname;
         }
 
         Ok(data)
     }
 
+    fn refresh_resources(&mut self, data: &mut SystemData) {
+        const GB: f64 = 1024.0 * 1024.0 * 1024.0;
+
+        self.sys.refresh_memory();
+        let total = self.sys.total_memory();
+        if total > 0 {
+            let used = total.saturating_sub(self.sys.available_memory());
+            data.memory_total_gb = total as f64 / GB;
+            data.memory_used_gb = used as f64 / GB;
+            data.memory_used_pct = (used as f64 / total as f64) * 100.0;
+        }
+
+        let swap_total = self.sys.total_swap();
+        if swap_total > 0 {
+            let swap_used = self.sys.used_swap();
+            data.swap_total_gb = swap_total as f64 / GB;
+            data.swap_used_gb = swap_used as f64 / GB;
+            data.swap_used_pct = (swap_used as f64 / swap_total as f64) * 100.0;
+        }
+
+        // Usage since the previous refresh; the very first call only takes a baseline
+        self.sys.refresh_cpu_usage();
+        if self.cpu_baseline_taken {
+            data.cpu_measured = true;
+            data.cpu_usage_pct = self.sys.global_cpu_usage() as f64;
+            data.cpu_cores_pct = self.sys.cpus().iter().map(|cpu| cpu.cpu_usage() as f64).collect();
+        }
+        self.cpu_baseline_taken = true;
+        data.cpu_count = self.sys.cpus().len();
+
+        let load = System::load_average();
+        data.load_1 = load.one;
+        data.load_5 = load.five;
+        data.load_15 = load.fifteen;
+
+        // Per-interface cumulative counters (excluding loopback)
+        self.networks.refresh(true);
+        data.net_interfaces = self
+            .networks
+            .iter()
+            .filter(|(name, _)| name.as_str() != "lo" && !name.starts_with("lo0"))
+            .map(|(name, net)| (name.clone(), (net.total_received(), net.total_transmitted())))
+            .collect();
+    }
+
     async fn fetch_external_block(&self) -> Result<u64> {
-        let url = format!("wss://rpc-{}.monadinfra.com", self.network);
-        let (ws_stream, _) = connect_async(&url)
+        let url = &self.external_rpc_url;
+        if url.starts_with("http://") || url.starts_with("https://") {
+            return self.fetch_external_block_http(url).await;
+        }
+
+        let (ws_stream, _) = connect_async(ws_request(url, self.auth_header.as_deref())?)
             .await
             .context("Failed to connect to external WebSocket")?;
 
@@ -170,52 +334,135 @@ impl SystemClient {
         while let Some(msg) = read.next().await {
             match msg {
                 Ok(Message::Text(text)) => {
-                    let response: serde_json::Value = serde_json::from_str(&text)?;
-                    if let Some(hex) = response["result"].as_str() {
-                        let hex = hex.trim_start_matches("0x");
-                        return Ok(u64::from_str_radix(hex, 16).unwrap_or(0));
-                    }
-                    return Ok(0);
+                    let response: serde_json::Value =
+                        serde_json::from_str(&text).context("Failed to parse external RPC response")?;
+                    return block_number(&response);
                 }
                 Ok(Message::Close(_)) => break,
-                Err(_) => break,
+                Err(e) => return Err(e).context("External WebSocket failed"),
                 _ => continue,
             }
         }
-        Ok(0)
+        bail!("External WebSocket closed without answering")
+    }
+
+    async fn fetch_external_block_http(&self, url: &str) -> Result<u64> {
+        let request = json!({
+            "jsonrpc": "2.0",
+            "method": "eth_blockNumber",
+            "params": [],
+            "id": 1
+        });
+
+        let mut builder = self.http.post(url).json(&request);
+        if let Some(auth) = &self.auth_header {
+            builder = builder.header(reqwest::header::AUTHORIZATION, auth);
+        }
+        let response: serde_json::Value = builder
+            .send()
+            .await
+            .context("Failed to reach external RPC")?
+            .json()
+            .await
+            .context("Failed to parse external RPC response")?;
+
+        block_number(&response)
     }
 }
 
-/// Returns (bft_active, execution_active, rpc_active, started_at_timestamp)
-fn fetch_services_status() -> (bool, bool, bool, u64) {
-    let bft = Command::new("systemctl")
-        .args(["is-active", "--quiet", "monad-bft"])
-        .status()
-        .map(|s| s.success())
-        .unwrap_or(false);
-
-    let execution = Command::new("systemctl")
-        .args(["is-active", "--quiet", "monad-execution"])
-        .status()
-        .map(|s| s.success())
-        .unwrap_or(false);
-
-    let rpc = Command::new("systemctl")
-        .args(["is-active", "--quiet", "monad-rpc"])
-        .status()
-        .map(|s| s.success())
-        .unwrap_or(false);
-
-    // Get service start time from monad-bft (parse ActiveEnterTimestamp)
-    let started_at = Command::new("systemctl")
-        .args(["show", "monad-bft", "--property=ActiveEnterTimestamp"])
-        .output()
-        .ok()
-        .and_then(|o| String::from_utf8(o.stdout).ok())
-        .and_then(|s| parse_systemd_timestamp(&s))
+/// Block number from an eth_blockNumber response, or why there isn't one
+fn block_number(response: &serde_json::Value) -> Result<u64> {
+    if let Some(message) = response["error"]["message"].as_str() {
+        bail!("External RPC error: {}", message);
+    }
+    let hex = response["result"]
+        .as_str()
+        .context("External RPC response has no block number")?;
+    match u64::from_str_radix(hex.trim_start_matches("0x"), 16) {
+        Ok(block) if block > 0 => Ok(block),
+        _ => bail!("External RPC returned an invalid block number: {}", hex),
+    }
+}
+
+/// Returns ([(unit, active)], started_at_timestamp) for the given units,
+/// None if systemctl isn't installed
+fn fetch_services_status(units: &[String]) -> Option<(Vec<(String, bool)>, u64)> {
+    let mut services = Vec::with_capacity(units.len());
+    for unit in units {
+        let active = match Command::new("systemctl")
+            .args(["is-active", "--quiet", unit])
+            .status()
+        {
+            Ok(status) => status.success(),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return None,
+            Err(_) => false,
+        };
+        services.push((unit.clone(), active));
+    }
+
+    // Uptime is tracked for the first configured unit (monad-bft by default)
+    let Some(primary) = units.first() else {
+        return Some((services, 0));
+    };
+
+    // The monotonic timestamp plus boot time is locale/timezone independent;
+    // fall back to parsing ActiveEnterTimestamp.
+    let started_at = fetch_started_at_monotonic(primary)
+        .or_else(|| {
+            Command::new("systemctl")
+                .args(["show", primary, "--property=ActiveEnterTimestamp"])
+                .output()
+                .ok()
+                .and_then(|o| String::from_utf8(o.stdout).ok())
+                .and_then(|s| parse_systemd_timestamp(&s))
+        })
         .unwrap_or(0);
 
-    (bft, execution, rpc, started_at)
+    Some((services, started_at))
+}
+
+/// VmRSS (kB) of a unit's MainPID, None if the service isn't running
+fn fetch_process_rss_kb(unit: &str) -> Option<u64> {
+    let output = Command::new("systemctl")
+        .args(["show", unit, "--property=MainPID"])
+        .output()
+        .ok()?;
+    let pid: u32 = String::from_utf8_lossy(&output.stdout)
+        .split('=')
+        .nth(1)?
+        .trim()
+        .parse()
+        .ok()?;
+    if pid == 0 {
+        return None;
+    }
+
+    let status = fs::read_to_string(format!("/proc/{}/status", pid)).ok()?;
+    status
+        .lines()
+        .find(|line| line.starts_with("VmRSS:"))
+        .and_then(|line| line.split_whitespace().nth(1))
+        .and_then(|v| v.parse().ok())
+}
+
+/// Service start time (seconds since epoch) from ActiveEnterTimestampMonotonic + boot time
+fn fetch_started_at_monotonic(unit: &str) -> Option<u64> {
+    let output = Command::new("systemctl")
+        .args(["show", unit, "--property=ActiveEnterTimestampMonotonic"])
+        .output()
+        .ok()?;
+    let monotonic_us = parse_monotonic_timestamp(&String::from_utf8_lossy(&output.stdout))?;
+    let boot_time = System::boot_time();
+    if boot_time == 0 {
+        return None;
+    }
+    Some(boot_time + monotonic_us / 1_000_000)
+}
+
+/// Parse "ActiveEnterTimestampMonotonic=123456789" (microseconds since boot, 0 if never active)
+fn parse_monotonic_timestamp(output: &str) -> Option<u64> {
+    let value: u64 = output.split('=').nth(1)?.trim().parse().ok()?;
+    (value > 0).then_some(value)
 }
 
 /// Parse systemd timestamp like "ActiveEnterTimestamp=Thu 2025-12-11 21:20:59 CET"
@@ -248,145 +495,106 @@ fn parse_systemd_timestamp(output: &str) -> Option<u64> {
         return None;
     }
 
-    // Simple approximation - convert to seconds since epoch
-    // This is a rough calculation but good enough for uptime display
-    let year = date_parts[0] as u64;
-    let month = date_parts[1] as u64;
-    let day = date_parts[2] as u64;
-    let hour = time_parts[0] as u64;
-    let min = time_parts[1] as u64;
-    let sec = time_parts[2] as u64;
-
-    // Days since epoch (Jan 1, 1970), simplified
-    let years_since_1970 = year.saturating_sub(1970);
-    let leap_years = (years_since_1970 + 1) / 4; // rough estimate
-    let days_in_prev_months: u64 = match month {
-        1 => 0,
-        2 => 31,
-        3 => 59,
-        4 => 90,
-        5 => 120,
-        6 => 151,
-        7 => 181,
-        8 => 212,
-        9 => 243,
-        10 => 273,
-        11 => 304,
-        12 => 334,
-        _ => 0,
-    };
+    let year = date_parts[0] as i64;
+    let month = date_parts[1];
+    let day = date_parts[2];
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+
+    let hour = time_parts[0] as i64;
+    let min = time_parts[1] as i64;
+    let sec = time_parts[2] as i64;
 
-    let total_days = years_since_1970 * 365 + leap_years + days_in_prev_months + day - 1;
-    let total_secs = total_days * 86400 + hour * 3600 + min * 60 + sec;
+    // Local wall-clock time; the zone token says how far it is from UTC
+    let offset_secs = parse_tz_offset(parts.get(3).copied().unwrap_or("UTC"))?;
+    let local_secs = days_from_civil(year, month, day) * 86400 + hour * 3600 + min * 60 + sec;
 
-    // Adjust for timezone (assume CET = UTC+1, subtract 1 hour)
-    Some(total_secs.saturating_sub(3600))
+    u64::try_from(local_secs - offset_secs).ok()
 }
 
-/// Returns (mem_pct, mem_used_gb, mem_total_gb, cpu_pct, net_rx, net_tx)
-fn fetch_system_resources() -> (f64, f64, f64, f64, u64, u64) {
-    let mut mem_pct = 0.0;
-    let mut mem_used_gb = 0.0;
-    let mut mem_total_gb = 0.0;
-    let mut cpu_pct = 0.0;
-    let mut net_rx: u64 = 0;
-    let mut net_tx: u64 = 0;
-
-    // Parse /proc/meminfo for memory
-    if let Ok(meminfo) = fs::read_to_string("/proc/meminfo") {
-        let mut total_kb: u64 = 0;
-        let mut available_kb: u64 = 0;
-
-        for line in meminfo.lines() {
-            if line.starts_with("MemTotal:") {
-                total_kb = line.split_whitespace().nth(1).and_then(|s| s.parse().ok()).unwrap_or(0);
-            } else if line.starts_with("MemAvailable:") {
-                available_kb = line.split_whitespace().nth(1).and_then(|s| s.parse().ok()).unwrap_or(0);
-            }
-        }
+/// Days since 1970-01-01 for a proleptic Gregorian date (Howard Hinnant's algorithm),
+/// so century years are only leap years when divisible by 400
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let month = month as i64;
+    let day_of_year = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day as i64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
 
-        if total_kb > 0 {
-            let used_kb = total_kb.saturating_sub(available_kb);
-            mem_total_gb = total_kb as f64 / 1024.0 / 1024.0;
-            mem_used_gb = used_kb as f64 / 1024.0 / 1024.0;
-            mem_pct = (used_kb as f64 / total_kb as f64) * 100.0;
+/// UTC offset in seconds for a timezone token: a numeric "+0100"/"-05:30" offset
+/// or a common abbreviation. Unknown abbreviations return None rather than a guess.
+fn parse_tz_offset(tz: &str) -> Option<i64> {
+    if let Some(sign) = tz.chars().next().filter(|c| *c == '+' || *c == '-') {
+        let digits: String = tz[1..].chars().filter(|c| *c != ':').collect();
+        if digits.len() != 4 {
+            return None;
         }
+        let hours: i64 = digits[..2].parse().ok()?;
+        let mins: i64 = digits[2..].parse().ok()?;
+        let secs = hours * 3600 + mins * 60;
+        return Some(if sign == '-' { -secs } else { secs });
     }
 
-    // Parse /proc/stat for CPU (simplified - just idle percentage)
-    if let Ok(stat) = fs::read_to_string("/proc/stat") {
-        if let Some(cpu_line) = stat.lines().next() {
-            let parts: Vec<u64> = cpu_line
-                .split_whitespace()
-                .skip(1) // skip "cpu"
-                .filter_map(|s| s.parse().ok())
-                .collect();
-
-            if parts.len() >= 4 {
-                let total: u64 = parts.iter().sum();
-                let idle = parts.get(3).unwrap_or(&0);
-                if total > 0 {
-                    cpu_pct = 100.0 - (*idle as f64 / total as f64 * 100.0);
-                }
-            }
-        }
-    }
+    let hours = match tz {
+        "UTC" | "GMT" | "Z" | "WET" => 0,
+        "BST" | "CET" | "WEST" => 1,
+        "CEST" | "EET" => 2,
+        "EEST" | "MSK" => 3,
+        "SGT" | "HKT" | "AWST" => 8,
+        "JST" | "KST" => 9,
+        "AEST" => 10,
+        "AEDT" => 11,
+        "EST" => -5,
+        "EDT" => -4,
+        "CDT" => -5,
+        "MST" => -7,
+        "MDT" => -6,
+        "PST" => -8,
+        "PDT" => -7,
+        _ => return None,
+    };
+    Some(hours * 3600)
+}
 
-    // Parse /proc/net/dev for network stats (sum all interfaces except lo)
-    if let Ok(netdev) = fs::read_to_string("/proc/net/dev") {
-        for line in netdev.lines().skip(2) {
-            let line = line.trim();
-            if line.starts_with("lo:") {
-                continue;
-            }
-            let parts: Vec<&str> = line.split_whitespace().collect();
-            if parts.len() >= 10 {
-                // Format: iface: rx_bytes rx_packets ... tx_bytes tx_packets ...
-                if let Ok(rx) = parts[1].parse::<u64>() {
-                    net_rx += rx;
-                }
-                if let Ok(tx) = parts[9].parse::<u64>() {
-                    net_tx += tx;
-                }
-            }
+/// /proc/diskstats always counts 512-byte sectors, whatever the device's block size
+const SECTOR_SIZE: f64 = 512.0;
+
+/// Returns cumulative (sectors_read, sectors_written) for the block device behind `path`
+fn read_disk_sectors(path: &str) -> Option<(u64, u64)> {
+    // /dev/triedb is usually a symlink to the real device (e.g. /dev/nvme1n1)
+    let device = fs::canonicalize(path).ok()?;
+    let name = device.file_name()?.to_str()?.to_string();
+
+    let diskstats = fs::read_to_string("/proc/diskstats").ok()?;
+    diskstats.lines().find_map(|line| {
+        // major minor name reads merged sectors_read ms writes merged sectors_written ...
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        if parts.len() < 10 || parts[2] != name {
+            return None;
         }
-    }
-
-    (mem_pct, mem_used_gb, mem_total_gb, cpu_pct, net_rx, net_tx)
+        Some((parts[5].parse().ok()?, parts[9].parse().ok()?))
+    })
 }
 
 fn parse_mpt_output(output: &str, data: &mut SystemData) {
     for line in output.lines() {
         let line = line.trim();
 
-        // Parse disk capacity/used line: "1.75 Tb      109.30 Gb  6.11%"
-        if line.contains("Tb") && line.contains("Gb") && line.contains('%') {
-            let parts: Vec<&str> = line.split_whitespace().collect();
-            if parts.len() >= 5 {
-                // Capacity
-                if let Ok(cap) = parts[0].parse::<f64>() {
-                    let unit = parts[1];
-                    data.disk_capacity_gb = match unit {
-                        "Tb" => cap * 1024.0,
-                        "Gb" => cap,
-                        _ => cap,
-                    };
-                }
-                // Used
-                if let Ok(used) = parts[2].parse::<f64>() {
-                    let unit = parts[3];
-                    data.disk_used_gb = match unit {
-                        "Tb" => used * 1024.0,
-                        "Gb" => used,
-                        "Mb" => used / 1024.0,
-                        _ => used,
-                    };
-                }
-                // Percentage
-                if let Ok(pct) = parts[4].trim_end_matches('%').parse::<f64>() {
-                    data.disk_used_pct = pct;
-                }
-            }
+        // Parse disk capacity/used line: "1.75 Tb      109.30 Gb  6.11%". The first two
+        // sizes are capacity and used, whatever their units; the percentage can be anywhere
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        let pct = parts
+            .iter()
+            .find_map(|part| part.strip_suffix('%').and_then(|pct| pct.parse::<f64>().ok()));
+        let sizes: Vec<f64> = parts.windows(2).filter_map(|pair| size_in_gb(pair[0], pair[1])).collect();
+        if let (Some(pct), [capacity, used, ..]) = (pct, sizes.as_slice()) {
+            data.disk_capacity_gb = *capacity;
+            data.disk_used_gb = *used;
+            data.disk_used_pct = pct;
         }
 
         // Parse history line: "MPT database has 637751 history, earliest is 41295350 latest is 41933100."
@@ -423,3 +631,102 @@ fn parse_mpt_output(output: &str, data: &mut SystemData) {
         }
     }
 }
+
+/// Convert a monad-mpt size such as "1.75" "Tb" to Gb (1024-based, like monad-mpt);
+/// None if `unit` isn't a size unit
+fn size_in_gb(value: &str, unit: &str) -> Option<f64> {
+    let multiplier = match unit.to_ascii_lowercase().as_str() {
+        "pb" => 1024.0 * 1024.0,
+        "tb" => 1024.0,
+        "gb" => 1.0,
+        "mb" => 1.0 / 1024.0,
+        "kb" => 1.0 / (1024.0 * 1024.0),
+        _ => return None,
+    };
+    value.replace(',', "").parse::<f64>().ok().map(|v| v * multiplier)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_systemd_timestamp() {
+        // 2025-12-11 20:20:59 UTC
+        let ts = parse_systemd_timestamp("ActiveEnterTimestamp=Thu 2025-12-11 21:20:59 CET");
+        assert_eq!(ts, Some(1765484459));
+
+        // Summer time is two hours ahead of UTC, not one
+        let ts = parse_systemd_timestamp("ActiveEnterTimestamp=Fri 2025-07-04 12:00:00 CEST");
+        assert_eq!(ts, Some(1751623200));
+
+        // 2100 is not a leap year, so March 1st follows February 28th
+        let feb_28 = parse_systemd_timestamp("ActiveEnterTimestamp=Sun 2100-02-28 00:00:00 UTC").unwrap();
+        let mar_1 = parse_systemd_timestamp("ActiveEnterTimestamp=Mon 2100-03-01 00:00:00 UTC").unwrap();
+        assert_eq!(mar_1 - feb_28, 86400);
+
+        assert_eq!(parse_systemd_timestamp("ActiveEnterTimestamp=n/a"), None);
+        assert_eq!(parse_systemd_timestamp("ActiveEnterTimestamp=Thu 2025-12-11 21:20:59 XYZ"), None);
+    }
+
+    const MPT_OUTPUT: &str = "\
+MPT database on storages:
+          Capacity           Used      %  Path
+           1.75 Tb      109.30 Gb  6.11%  \"/dev/nvme1n1p1\"
+MPT database internal lists:
+     Fast: 1,134 chunks with capacity 281.94 Gb used 280.63 Gb
+     Slow: 11 chunks with capacity 2.73 Gb used 1.30 Gb
+     Free: 5,990 chunks with capacity 1.46 Tb used 0.00 bytes
+MPT database has 637751 history, earliest is 41295350 latest is 41933100.
+     It has been configured to retain no more than 33554432.
+     Latest finalized is 41933098, latest verified is 41933095, latest voted is 41933099
+";
+
+    #[test]
+    fn test_parse_mpt_output() {
+        let mut data = SystemData::default();
+        parse_mpt_output(MPT_OUTPUT, &mut data);
+
+        assert_eq!(data.disk_capacity_gb, 1.75 * 1024.0);
+        assert_eq!(data.disk_used_gb, 109.30);
+        assert_eq!(data.disk_used_pct, 6.11);
+        assert_eq!(data.history_count, 637751);
+        assert_eq!(data.history_earliest, 41295350);
+        assert_eq!(data.history_latest, 41933100);
+        assert_eq!(data.latest_finalized, 41933098);
+        assert_eq!(data.latest_verified, 41933095);
+    }
+
+    #[test]
+    fn test_parse_mpt_output_units() {
+        let disk = |line: &str| {
+            let mut data = SystemData::default();
+            parse_mpt_output(line, &mut data);
+            (data.disk_capacity_gb, data.disk_used_gb, data.disk_used_pct)
+        };
+        assert_eq!(disk("  3.50 Tb   1.25 Tb  35.71%  \"/dev/nvme1n1p1\""), (3584.0, 1280.0, 35.71));
+        // A freshly formatted device reports usage in Mb
+        assert_eq!(disk("  1.75 Tb   512.00 Mb  0.03%"), (1792.0, 0.5, 0.03));
+        assert_eq!(disk("  2.00 PB   1.00 TB  0.05%"), (2.0 * 1024.0 * 1024.0, 1024.0, 0.05));
+        // Percentage first
+        assert_eq!(disk("6.11%  1.75 tb  109.30 gb"), (1792.0, 109.30, 6.11));
+    }
+
+    #[test]
+    fn test_parse_mpt_output_ignores_garbage() {
+        let mut data = SystemData::default();
+        parse_mpt_output("error: could not open \"/dev/triedb\"\nTb Gb %\n", &mut data);
+        assert_eq!(data.disk_capacity_gb, 0.0);
+        assert_eq!(data.history_count, 0);
+    }
+
+    #[test]
+    fn test_block_number() {
+        assert_eq!(block_number(&json!({"jsonrpc": "2.0", "id": 1, "result": "0x3e8"})).unwrap(), 1_000);
+
+        let err = block_number(&json!({"error": {"code": -32601, "message": "method not found"}})).unwrap_err();
+        assert!(err.to_string().contains("method not found"), "{}", err);
+        assert!(block_number(&json!({"result": null})).is_err());
+        assert!(block_number(&json!({"result": "0x0"})).is_err());
+    }
+}
@@ -3,20 +3,28 @@ use ratatui::{
     style::{Color, Modifier, Style, Stylize},
     symbols,
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph, Row, Sparkline, Table},
+    widgets::{Block, Borders, Cell, Clear, Gauge, Paragraph, Row, Sparkline, Table},
     Frame,
 };
+use std::time::Duration;
 
-use crate::state::{AppState, Theme};
+use crate::alerts::AlertKind;
+use crate::config::{CustomTheme, Network};
+use crate::state::{AppState, DataSource, GraphMetric, HitAreas, Theme};
 
 // Monad brand colors
 const MONAD_PRIMARY: Color = Color::Rgb(110, 84, 255);  // #6E54FF
 
-/// Get colors based on current theme
+/// Get colors based on current theme (`custom` is the configured palette for Theme::Custom)
 /// Returns (title, label, value, text_dim, sparkline)
-fn get_colors(theme: Theme) -> (Color, Color, Color, Color, Color) {
+fn get_colors(theme: Theme, custom: Option<&CustomTheme>) -> (Color, Color, Color, Color, Color) {
+    if let (Theme::Custom, Some(c)) = (theme, custom) {
+        return (c.title, c.label, c.value, c.text_dim, c.sparkline);
+    }
+
     match theme {
-        Theme::Gray => (
+        // Custom falls back to gray if no palette is configured
+        Theme::Gray | Theme::Custom => (
             MONAD_PRIMARY,                    // title
             Color::Rgb(160, 160, 160),        // label
             Color::Rgb(220, 220, 220),        // value
@@ -58,36 +66,371 @@ fn get_colors(theme: Theme) -> (Color, Color, Color, Color, Color) {
             Color::Rgb(178, 34, 34),          // text_dim - firebrick red
             Color::Rgb(220, 20, 60),          // sparkline - crimson
         ),
+        // Canonical Dracula colors; meant for a #282a36 terminal background
+        Theme::Dracula => (
+            Color::Rgb(189, 147, 249),        // title - purple #bd93f9
+            Color::Rgb(139, 233, 253),        // label - cyan #8be9fd
+            Color::Rgb(248, 248, 242),        // value - foreground #f8f8f2
+            Color::Rgb(98, 114, 164),         // text_dim - comment #6272a4
+            Color::Rgb(80, 250, 123),         // sparkline - green #50fa7b
+        ),
+        // Solarized Dark accents; meant for a base03 (#002b36) terminal background
+        Theme::Solarized => (
+            Color::Rgb(38, 139, 210),         // title - blue #268bd2
+            Color::Rgb(131, 148, 150),        // label - base0 #839496
+            Color::Rgb(147, 161, 161),        // value - base1 #93a1a1
+            Color::Rgb(88, 110, 117),         // text_dim - base01 #586e75
+            Color::Rgb(42, 161, 152),         // sparkline - cyan #2aa198
+        ),
+        // Pure white text (21:1 on black) with a saturated accent
+        Theme::HighContrast => (
+            Color::Rgb(255, 255, 0),          // title - yellow
+            Color::Rgb(255, 255, 255),        // label - white
+            Color::Rgb(255, 255, 255),        // value - white
+            Color::Rgb(255, 255, 255),        // text_dim - white
+            Color::Rgb(0, 255, 255),          // sparkline - cyan
+        ),
     }
 }
 
-pub fn draw(frame: &mut Frame, state: &AppState) {
+/// Smallest terminal the full dashboard fits in (header, stats, graph, a few block rows, footer)
+const MIN_WIDTH: u16 = 60;
+const MIN_HEIGHT: u16 = 20;
+
+/// Draw the focused node's dashboard; with several nodes, a strip listing all of them
+/// goes above it (or, in compact mode, one compact panel per node, and in split view
+/// the focused node next to the following one)
+pub fn draw(frame: &mut Frame, nodes: &mut [AppState], focused: usize) {
     let area = frame.area();
-    let (title_color, label_color, value_color, text_dim, sparkline_color) = get_colors(state.theme);
+    let state = &nodes[focused];
+    let (title_color, label_color, value_color, _, _) = get_colors(state.theme, state.config.custom_theme.as_ref());
+    let no_color = state.config.no_color;
+
+    if nodes.len() == 1 {
+        draw_node(frame, area, &mut nodes[0]);
+    } else if state.compact {
+        let panels = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(vec![Constraint::Ratio(1, nodes.len() as u32); nodes.len()])
+            .split(area);
+        for (node, panel) in nodes.iter_mut().zip(panels.iter()) {
+            node.hit_areas = HitAreas::default();
+            draw_compact(frame, *panel, node, title_color, label_color, value_color);
+        }
+    } else if state.split_view {
+        let other = (focused + 1) % nodes.len();
+        draw_split(frame, area, nodes, focused, other);
+    } else {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(nodes.len() as u16 + 2), Constraint::Min(0)])
+            .split(area);
+        draw_node_strip(frame, chunks[0], nodes, focused, title_color, label_color, value_color);
+        draw_node(frame, chunks[1], &mut nodes[focused]);
+    }
+
+    // Popups belong to the focused node, whatever the layout
+    let state = &nodes[focused];
+    if state.show_block_detail {
+        draw_block_detail(frame, area, state, title_color, label_color, value_color);
+    }
+    if state.show_error_log {
+        draw_error_log(frame, area, state, title_color, label_color, value_color);
+    }
+    if state.show_help {
+        draw_help(frame, area, state, title_color, label_color, value_color);
+    }
+
+    if no_color {
+        strip_colors(frame);
+    }
+}
+
+/// Two nodes side by side, each with its header and blocks table, between a line
+/// comparing their block heights and the focused (left) node's footer
+fn draw_split(frame: &mut Frame, area: Rect, nodes: &mut [AppState], left: usize, right: usize) {
+    let (title_color, label_color, value_color, text_dim, _) =
+        get_colors(nodes[left].theme, nodes[left].config.custom_theme.as_ref());
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Min(0), Constraint::Length(3)])
+        .split(area);
+
+    // Positive when the left node is ahead
+    let (left_height, right_height) = (nodes[left].block_height(), nodes[right].block_height());
+    let divergence = left_height as i64 - right_height as i64;
+    let divergence_color = match divergence.unsigned_abs() {
+        0..=2 => Color::Green,
+        3..=10 => Color::Yellow,
+        _ => Color::Red,
+    };
+    let name = |node: &AppState| {
+        if node.system.node_id.is_empty() { "...".to_string() } else { node.system.node_id.clone() }
+    };
+    let comparison = Line::from(vec![
+        Span::styled(format!("{} ", name(&nodes[left])), Style::default().fg(title_color).bold()),
+        Span::styled(format_number(left_height), Style::default().fg(value_color)),
+        Span::styled("  vs  ", Style::default().fg(label_color)),
+        Span::styled(format!("{} ", name(&nodes[right])), Style::default().fg(title_color).bold()),
+        Span::styled(format_number(right_height), Style::default().fg(value_color)),
+        Span::styled("  Δ ", Style::default().fg(label_color)),
+        Span::styled(format!("{:+} blocks", divergence), Style::default().fg(divergence_color).bold()),
+    ]);
+    let block = Block::default()
+        .title(" COMPARE ")
+        .title_style(Style::default().fg(label_color))
+        .title_bottom(Line::from(Span::styled(" s: full view  n: next node ", Style::default().fg(label_color))))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(label_color));
+    frame.render_widget(Paragraph::new(comparison).alignment(Alignment::Center).block(block), chunks[0]);
+
+    let halves = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(chunks[1]);
+    for (index, half) in [(left, halves[0]), (right, halves[1])] {
+        let node = &mut nodes[index];
+        node.hit_areas = HitAreas::default();
+        let panels = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(5), Constraint::Min(0)])
+            .split(half);
+        draw_header(frame, panels[0], node, title_color, label_color, value_color);
+        let block_rows = draw_blocks(frame, panels[1], node, label_color, text_dim);
+        mark_stale(frame, panels[0], node, DataSource::Metrics);
+        mark_stale(frame, panels[1], node, DataSource::Rpc);
+        if index == left {
+            node.hit_areas.block_rows = block_rows;
+        }
+    }
+
+    // Quit prompt, notices and errors for the node that keys act on
+    let state = &mut nodes[left];
+    state.hit_areas.theme_label = draw_footer(frame, chunks[2], state, label_color, value_color);
+}
+
+/// One line per node, the focused one marked, so trouble on another node is visible
+fn draw_node_strip(
+    frame: &mut Frame,
+    area: Rect,
+    nodes: &[AppState],
+    focused: usize,
+    title_color: Color,
+    label_color: Color,
+    value_color: Color,
+) {
+    let lines: Vec<Line> = nodes
+        .iter()
+        .enumerate()
+        .map(|(i, node)| {
+            let marker = if i == focused { "▶ " } else { "  " };
+            let mut spans = vec![Span::styled(marker, Style::default().fg(title_color))];
+            spans.extend(compact_node_line(node, title_color, label_color, value_color).spans);
+            if !node.alerts.active().is_empty() {
+                spans.push(Span::styled(
+                    format!("  ⚠ {} alert(s)", node.alerts.active().len()),
+                    Style::default().fg(Color::Red).bold(),
+                ));
+            }
+            Line::from(spans)
+        })
+        .collect();
+
+    let block = Block::default()
+        .title(" NODES ")
+        .title_style(Style::default().fg(label_color))
+        .title_bottom(Line::from(Span::styled(" n: next node  s: side by side ", Style::default().fg(label_color))))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(label_color));
+
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+/// The full dashboard for one node within `area`
+fn draw_node(frame: &mut Frame, area: Rect, state: &mut AppState) {
+    let (title_color, label_color, value_color, text_dim, sparkline_color) = get_colors(state.theme, state.config.custom_theme.as_ref());
+
+    if state.compact {
+        state.hit_areas = HitAreas::default();
+        draw_compact(frame, area, state, title_color, label_color, value_color);
+        return;
+    }
+
+    // Below this the fixed-height panels overlap, so ask for more room instead
+    if area.width < MIN_WIDTH || area.height < MIN_HEIGHT {
+        state.hit_areas = HitAreas::default();
+        let message = format!("terminal too small (need {}×{})", MIN_WIDTH, MIN_HEIGHT);
+        let y = area.y + area.height / 2;
+        let line = Rect::new(area.x, y, area.width, area.height.min(1));
+        frame.render_widget(
+            Paragraph::new(message).alignment(Alignment::Center).style(Style::default().fg(label_color)),
+            line,
+        );
+        return;
+    }
 
     // Draw festive lights border for Christmas theme
     if state.theme == Theme::Christmas {
         draw_festive_lights(frame, area);
     }
 
-    // Main layout: header, secondary stats, sparkline, blocks, footer
+    // Secondary stats wrap onto extra lines on narrow terminals (minus margin and borders)
+    let stats_lines = secondary_stats_lines(state, label_color, value_color, area.width.saturating_sub(4));
+    let stats_height = stats_lines.len().max(1) as u16 + 2;
+
+    // Sync progress only takes space while the node is catching up
+    let sync_height = if state.metrics.is_synced() { 0 } else { 3 };
+
+    // Full-width banner when the node drifts far from the reference block
+    let block_diff = state.system.block_difference(state.block_height());
+    let behind_height = if block_diff.unsigned_abs() > state.config.thresholds.behind_banner_blocks { 1 } else { 0 };
+
+    // Alert banner only while something is firing
+    let alert_height = if state.alerts.active().is_empty() { 0 } else { 3 };
+
+    // Main layout: header, alerts, sync progress, secondary stats, graph, blocks, footer
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .margin(1)
         .constraints([
+            Constraint::Length(behind_height),  // Node-behind banner
             Constraint::Length(5),  // Header stats (block, peers, tps, latency)
-            Constraint::Length(3),  // Secondary stats (disk, services, diff, epoch)
-            Constraint::Length(5),  // TPS sparkline
+            Constraint::Length(alert_height),  // Alert banner (hidden when nothing fires)
+            Constraint::Length(sync_height),  // Statesync progress (hidden once synced)
+            Constraint::Length(stats_height),  // Secondary stats (disk, services, diff, epoch)
+            Constraint::Length(5),  // Graph (TPS / memory / CPU / latency / network / gas / pool)
             Constraint::Min(6),     // Recent blocks
             Constraint::Length(3),  // Footer
         ])
         .split(area);
 
-    draw_header(frame, chunks[0], state, title_color, label_color, value_color);
-    draw_secondary_stats(frame, chunks[1], state, label_color, value_color);
-    draw_sparkline(frame, chunks[2], state, label_color, sparkline_color);
-    draw_blocks(frame, chunks[3], state, label_color, text_dim);
-    draw_footer(frame, chunks[4], state, label_color, value_color);
+    if behind_height > 0 {
+        draw_behind_banner(frame, chunks[0], block_diff);
+    }
+    draw_header(frame, chunks[1], state, title_color, label_color, value_color);
+    if alert_height > 0 {
+        draw_alerts(frame, chunks[2], state);
+    }
+    if sync_height > 0 {
+        draw_sync_progress(frame, chunks[3], state, label_color, value_color, sparkline_color);
+    }
+    let stats_border = alert_border_color(state, &[AlertKind::DiskFull, AlertKind::ServiceDown], label_color);
+    draw_secondary_stats(frame, chunks[4], stats_lines, stats_border);
+    draw_sparkline(frame, chunks[5], state, label_color, sparkline_color);
+    let block_rows = draw_blocks(frame, chunks[6], state, label_color, text_dim);
+
+    // Frozen numbers look healthy, so dim panels whose source stopped updating
+    let graph_source = match state.graph_metric {
+        GraphMetric::Tps | GraphMetric::Latency | GraphMetric::Pool => DataSource::Metrics,
+        GraphMetric::Memory | GraphMetric::Cpu | GraphMetric::Network => DataSource::System,
+        GraphMetric::Gas => DataSource::Rpc,
+    };
+    mark_stale(frame, chunks[1], state, DataSource::Metrics);
+    mark_stale(frame, chunks[4], state, DataSource::System);
+    mark_stale(frame, chunks[5], state, graph_source);
+    mark_stale(frame, chunks[6], state, DataSource::Rpc);
+    let theme_label = draw_footer(frame, chunks[7], state, label_color, value_color);
+
+    state.hit_areas = HitAreas { theme_label, block_rows };
+}
+
+/// Monochrome mode: drop every color, keeping only text attributes. Highlights that
+/// relied on a background become reversed, and red (bad) values become bold.
+fn strip_colors(frame: &mut Frame) {
+    for cell in frame.buffer_mut().content.iter_mut() {
+        let mut modifier = cell.modifier;
+        if cell.bg != Color::Reset {
+            modifier |= Modifier::REVERSED;
+        }
+        if cell.fg == Color::Red {
+            modifier |= Modifier::BOLD;
+        }
+        cell.set_style(Style::reset().add_modifier(modifier));
+    }
+}
+
+/// Minimal layout for tiled dashboards: a node line, a host line and (when firing) an alert line
+fn draw_compact(frame: &mut Frame, area: Rect, state: &AppState, title_color: Color, label_color: Color, value_color: Color) {
+    let sys = &state.system;
+    let sep = || Span::styled(" | ", Style::default().fg(label_color));
+
+    let node_line = compact_node_line(state, title_color, label_color, value_color);
+
+    let mut host_spans = vec![
+        Span::styled("CPU ", Style::default().fg(label_color)),
+        // Remote nodes never get host stats
+        if sys.cpu_measured {
+            Span::styled(format!("{:.0}%", sys.cpu_usage_pct), Style::default().fg(usage_color(sys.cpu_usage_pct)))
+        } else {
+            Span::styled("n/a", Style::default().fg(label_color).add_modifier(Modifier::DIM))
+        },
+        sep(),
+        Span::styled("MEM ", Style::default().fg(label_color)),
+        if sys.memory_total_gb > 0.0 {
+            Span::styled(format!("{:.0}%", sys.memory_used_pct), Style::default().fg(usage_color(sys.memory_used_pct)))
+        } else {
+            Span::styled("n/a", Style::default().fg(label_color).add_modifier(Modifier::DIM))
+        },
+        sep(),
+        Span::styled("DISK ", Style::default().fg(label_color)),
+        if sys.disk_data_available {
+            Span::styled(format!("{:.0}%", sys.disk_used_pct), Style::default().fg(usage_color(sys.disk_used_pct)))
+        } else {
+            Span::styled("n/a", Style::default().fg(label_color).add_modifier(Modifier::DIM))
+        },
+        sep(),
+        Span::styled("SVC", Style::default().fg(label_color)),
+    ];
+    if !sys.services_available {
+        host_spans.push(Span::styled(" n/a", Style::default().fg(label_color).add_modifier(Modifier::DIM)));
+    }
+    for (unit, active) in &sys.services {
+        let (mark, color) = if *active { ("✓", Color::Green) } else { ("✗", Color::Red) };
+        host_spans.push(Span::styled(format!(" {}{}", service_short_name(unit), mark), Style::default().fg(color)));
+    }
+
+    let mut lines = vec![node_line, Line::from(host_spans)];
+    if !state.alerts.active().is_empty() {
+        let message = state.alerts.active().iter().map(|a| a.message.as_str()).collect::<Vec<_>>().join(" | ");
+        lines.push(Line::from(Span::styled(format!("⚠ {}", message), Style::default().fg(Color::Red).bold())));
+    }
+    if state.confirming_quit {
+        lines.push(Line::from(Span::styled("Quit? (y/n)", Style::default().fg(Color::Yellow).bold())));
+    }
+
+    frame.render_widget(Paragraph::new(lines), area);
+}
+
+/// Node name, block height and difference, peers, TPS and latency on one line
+fn compact_node_line(state: &AppState, title_color: Color, label_color: Color, value_color: Color) -> Line<'static> {
+    let sys = &state.system;
+    let sep = || Span::styled(" | ", Style::default().fg(label_color));
+
+    let block_diff = state.system.block_difference(state.block_height());
+    let peer_color = match state.peer_health() {
+        "healthy" => Color::Green,
+        "ok" => Color::Yellow,
+        _ => Color::Red,
+    };
+    Line::from(vec![
+        Span::styled(
+            format!("{} ", if sys.node_id.is_empty() { "..." } else { &sys.node_id }),
+            Style::default().fg(title_color).bold(),
+        ),
+        Span::styled("#", Style::default().fg(label_color)),
+        Span::styled(format_number(state.block_height()), Style::default().fg(value_color).bold()),
+        Span::styled(format!(" ({})", reference_text(state, block_diff)), Style::default().fg(label_color)),
+        sep(),
+        Span::styled("PEERS ", Style::default().fg(label_color)),
+        Span::styled(state.metrics.peer_count.to_string(), Style::default().fg(peer_color)),
+        sep(),
+        Span::styled("TPS ", Style::default().fg(label_color)),
+        Span::styled(format!("{:.0}", state.tps_ema), Style::default().fg(value_color)),
+        sep(),
+        Span::styled("LAT ", Style::default().fg(label_color)),
+        Span::styled(format!("{}ms", state.metrics.latency_p99_ms), Style::default().fg(value_color)),
+    ])
 }
 
 fn draw_festive_lights(frame: &mut Frame, area: Rect) {
@@ -106,6 +449,10 @@ fn draw_festive_lights(frame: &mut Frame, area: Rect) {
         .map(|d| d.as_millis() / 1000)
         .unwrap_or(0)) as usize;
 
+    // The bottom and right edges are at height - 1 / width - 1
+    if area.width == 0 || area.height == 0 {
+        return;
+    }
     let width = area.width as usize;
     let height = area.height as usize;
 
@@ -114,7 +461,7 @@ fn draw_festive_lights(frame: &mut Frame, area: Rect) {
         // Top edge - every 3rd position
         if x % 3 == 0 {
             let color_idx = (x / 3 + tick) % light_colors.len();
-            let bright = ((x / 3 + tick) % 4) != 0;  // 75% on
+            let bright = !(x / 3 + tick).is_multiple_of(4);  // 75% on
             if bright {
                 let span = Span::styled("*", Style::default().fg(light_colors[color_idx]));
                 frame.render_widget(Paragraph::new(span), Rect::new(area.x + x as u16, area.y, 1, 1));
@@ -124,7 +471,7 @@ fn draw_festive_lights(frame: &mut Frame, area: Rect) {
         // Bottom edge - every 3rd position, offset
         if (x + 1) % 3 == 0 {
             let color_idx = (x / 3 + tick + 2) % light_colors.len();
-            let bright = ((x / 3 + tick + 1) % 4) != 0;
+            let bright = !(x / 3 + tick + 1).is_multiple_of(4);
             if bright {
                 let span = Span::styled("*", Style::default().fg(light_colors[color_idx]));
                 frame.render_widget(Paragraph::new(span), Rect::new(area.x + x as u16, area.y + area.height - 1, 1, 1));
@@ -136,7 +483,7 @@ fn draw_festive_lights(frame: &mut Frame, area: Rect) {
         // Left edge - every 2nd position
         if y % 2 == 0 {
             let color_idx = (y / 2 + tick + 1) % light_colors.len();
-            let bright = ((y / 2 + tick) % 4) != 0;
+            let bright = !(y / 2 + tick).is_multiple_of(4);
             if bright {
                 let span = Span::styled("*", Style::default().fg(light_colors[color_idx]));
                 frame.render_widget(Paragraph::new(span), Rect::new(area.x, area.y + y as u16, 1, 1));
@@ -146,7 +493,7 @@ fn draw_festive_lights(frame: &mut Frame, area: Rect) {
         // Right edge - every 2nd position, offset
         if (y + 1) % 2 == 0 {
             let color_idx = (y / 2 + tick + 3) % light_colors.len();
-            let bright = ((y / 2 + tick + 2) % 4) != 0;
+            let bright = !(y / 2 + tick + 2).is_multiple_of(4);
             if bright {
                 let span = Span::styled("*", Style::default().fg(light_colors[color_idx]));
                 frame.render_widget(Paragraph::new(span), Rect::new(area.x + area.width - 1, area.y + y as u16, 1, 1));
@@ -156,6 +503,8 @@ fn draw_festive_lights(frame: &mut Frame, area: Rect) {
 }
 
 fn draw_header(frame: &mut Frame, area: Rect, state: &AppState, title_color: Color, label_color: Color, value_color: Color) {
+    let symbols = state.theme.uses_symbols();
+
     // Pulsing heartbeat - smooth color fade from brand purple to light
     let pulse = state.pulse_intensity();
 
@@ -166,31 +515,61 @@ fn draw_header(frame: &mut Frame, area: Rect, state: &AppState, title_color: Col
         (254.0 + 1.0 * pulse) as u8,    // B: 254 -> 255
     );
 
-    // Shorten node_id if too long (take last part after last hyphen or first 12 chars)
-    let node_id_display = if state.system.node_id.is_empty() {
-        "...".to_string()
-    } else if state.system.node_id.len() > 16 {
-        // Take last segment after hyphen or truncate
-        state.system.node_id
-            .rsplit('-')
-            .next()
-            .unwrap_or(&state.system.node_id[..12])
-            .to_string()
-    } else {
-        state.system.node_id.clone()
-    };
+    let node_id_display = short_node_id(&state.system.node_id);
+
+    // Block age gauge next to the heartbeat; past the threshold the heartbeat stops
+    // pulsing and both flash red
+    let mut age_spans = Vec::new();
+    let mut heartbeat_color = pulse_color;
+    if let Some(age) = state.time_since_last_block() {
+        let crit_secs = state.config.thresholds.block_age_crit_secs.max(1) as f64;
+        let ratio = age.as_secs_f64() / crit_secs;
+        let age_color = if ratio >= 1.0 {
+            heartbeat_color = if flash_on() { Color::Red } else { label_color };
+            heartbeat_color
+        } else if ratio >= 0.5 {
+            Color::Yellow
+        } else {
+            Color::Green
+        };
+        age_spans.push(Span::styled(format!(" {}", block_age_bar(ratio)), Style::default().fg(age_color)));
+        age_spans.push(Span::styled(format!(" {:.1}s", age.as_secs_f64()), Style::default().fg(age_color)));
+    }
 
-    let title = Line::from(vec![
+    let mut title = vec![
         Span::styled(" monad-monitor ", Style::default().fg(title_color).bold()),
-        Span::styled("●", Style::default().fg(pulse_color)),
-        Span::styled(" MAINNET ", Style::default().fg(Color::Green).bold()),
+        Span::styled("●", Style::default().fg(heartbeat_color)),
+    ];
+    title.extend(age_spans);
+    title.extend([
+        Span::styled(
+            format!(" {} ", state.config.network.name().to_uppercase()),
+            Style::default().fg(if state.config.network == Network::Testnet { Color::Yellow } else { Color::Green }).bold(),
+        ),
         Span::styled(format!("[{}] ", node_id_display), Style::default().fg(label_color)),
     ]);
+    let title = Line::from(title);
+
+    let header_alerts = [AlertKind::LowPeers, AlertKind::HighLatency, AlertKind::BlockBehind, AlertKind::StaleBlock];
+    // One dot per data source, so a failing scrape isn't hidden behind a healthy RPC
+    let mut health = vec![Span::raw(" ")];
+    for source in DataSource::ALL {
+        let color = match state.source_ok(source) {
+            Some(false) => Color::Red,
+            Some(true) if state.stale_for(source).is_some() => Color::Yellow,
+            Some(true) if source == DataSource::Metrics && state.metrics_frozen() => Color::Yellow,
+            Some(true) => Color::Green,
+            None => label_color,
+        };
+        health.push(Span::styled("●", Style::default().fg(color)));
+        health.push(Span::styled(format!(" {} ", source.name()), Style::default().fg(label_color)));
+    }
 
     let block = Block::default()
         .title(title)
+        .title(Line::from(health).right_aligned())
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(label_color));
+        .border_style(Style::default().fg(alert_border_color(state, &header_alerts, label_color)));
 
     let inner = block.inner(area);
     frame.render_widget(block, area);
@@ -218,12 +597,13 @@ fn draw_header(frame: &mut Frame, area: Rect, state: &AppState, title_color: Col
         Color::Red
     };
 
-    let diff_str = if block_diff == 0 {
-        "Δ0".to_string()
-    } else if block_diff > 0 {
-        format!("Δ-{}", block_diff)
+    let diff_str = reference_text(state, block_diff);
+
+    // The reference block is cached between fetches; dim it once it's too old to trust
+    let diff_style = if state.external_block_stale() {
+        Style::default().fg(label_color).add_modifier(Modifier::DIM)
     } else {
-        format!("Δ+{}", block_diff.abs())
+        Style::default().fg(label_color)
     };
 
     let block_text = vec![
@@ -233,9 +613,10 @@ fn draw_header(frame: &mut Frame, area: Rect, state: &AppState, title_color: Col
             Style::default().fg(value_color).bold(),
         )),
         Line::from(vec![
-            Span::styled("✓ ", Style::default().fg(sync_color)),
+            Span::styled(if symbols { status_symbol(sync_color) } else { "✓" }, Style::default().fg(sync_color)),
+            Span::raw(" "),
             Span::styled(sync_status, Style::default().fg(sync_color)),
-            Span::styled(format!(" ({})", diff_str), Style::default().fg(label_color)),
+            Span::styled(format!(" ({})", diff_str), diff_style),
         ]),
     ];
     frame.render_widget(Paragraph::new(block_text).alignment(Alignment::Center), columns[0]);
@@ -263,15 +644,17 @@ fn draw_header(frame: &mut Frame, area: Rect, state: &AppState, title_color: Col
             Span::styled(format!(" {}", peer_trend_arrow), Style::default().fg(peer_trend_color)),
         ]),
         Line::from(vec![
-            Span::styled("↑ ", Style::default().fg(peer_color)),
+            Span::styled(if symbols { status_symbol(peer_color) } else { "↑" }, Style::default().fg(peer_color)),
+            Span::raw(" "),
             Span::styled(peer_health, Style::default().fg(peer_color)),
         ]),
     ];
     frame.render_widget(Paragraph::new(peer_text).alignment(Alignment::Center), columns[1]);
 
-    // TPS with peak and trend
-    let tps = state.tps;
-    let tps_peak = state.tps_peak;
+    // Smoothed TPS as the headline (the sparkline keeps the raw readings), with peak and trend
+    let tps = state.tps_ema;
+    let (peak_label, tps_peak) =
+        if state.all_time_peak { ("all", state.tps_peak) } else { ("10m", state.tps_recent_peak()) };
     let tps_trend = state.tps_trend();
 
     let (trend_arrow, trend_color) = match tps_trend {
@@ -286,7 +669,7 @@ fn draw_header(frame: &mut Frame, area: Rect, state: &AppState, title_color: Col
             Span::styled(format!("{:.0}", tps), Style::default().fg(MONAD_PRIMARY).bold()),
             Span::styled(format!(" {}", trend_arrow), Style::default().fg(trend_color)),
         ]),
-        Line::from(Span::styled(format!("peak: {:.0}", tps_peak), Style::default().fg(label_color))),
+        Line::from(Span::styled(format!("peak {}: {:.0}", peak_label, tps_peak), Style::default().fg(label_color))),
     ];
     frame.render_widget(Paragraph::new(tps_text).alignment(Alignment::Center), columns[2]);
 
@@ -311,24 +694,173 @@ fn draw_header(frame: &mut Frame, area: Rect, state: &AppState, title_color: Col
     let latency_text = vec![
         Line::from(Span::styled("LATENCY", Style::default().fg(label_color))),
         Line::from(vec![
-            Span::styled(format!("{}ms", latency), Style::default().fg(latency_color).bold()),
+            status_span(format!("{}ms", latency), latency_color, symbols).bold(),
             Span::styled(format!(" {}", trend_arrow), Style::default().fg(trend_color)),
         ]),
-        Line::from(Span::styled("p99", Style::default().fg(label_color))),
+        Line::from(Span::styled(
+            if state.metrics.latency_p95_ms > 0 {
+                format!("p99  p50 {} p95 {}", state.metrics.latency_p50_ms, state.metrics.latency_p95_ms)
+            } else {
+                "p99".to_string()
+            },
+            Style::default().fg(label_color),
+        )),
     ];
     frame.render_widget(Paragraph::new(latency_text).alignment(Alignment::Center), columns[3]);
 }
 
-fn draw_secondary_stats(frame: &mut Frame, area: Rect, state: &AppState, label_color: Color, value_color: Color) {
+/// Node id for the header: long ones (e.g. configured names) are cut to the part after
+/// the last hyphen, or else to their first 12 characters
+fn short_node_id(node_id: &str) -> String {
+    if node_id.is_empty() {
+        "...".to_string()
+    } else if node_id.chars().count() > 16 {
+        match node_id.rsplit_once('-').map(|(_, last)| last).filter(|last| !last.is_empty()) {
+            Some(last) => last.to_string(),
+            None => node_id.chars().take(12).collect(),
+        }
+    } else {
+        node_id.to_string()
+    }
+}
+
+/// Describe `SystemData::block_difference` (external minus local) from the node's side:
+/// positive means the node is behind the network
+fn format_block_difference(block_diff: i64) -> String {
+    match block_diff {
+        0 => "in sync".to_string(),
+        d if d > 0 => format!("-{} behind", format_number(d as u64)),
+        d => format!("+{} ahead", format_number(d.unsigned_abs())),
+    }
+}
+
+/// The block difference, or why there is none (rather than a misleading "in sync")
+fn reference_text(state: &AppState, block_diff: i64) -> String {
+    match &state.system.external_error {
+        Some(_) if state.system.external_block == 0 => "reference RPC unreachable".to_string(),
+        _ => format_block_difference(block_diff),
+    }
+}
+
+fn draw_behind_banner(frame: &mut Frame, area: Rect, block_diff: i64) {
+    let message = if block_diff > 0 {
+        format!("⚠ NODE FALLING BEHIND: {} blocks", format_number(block_diff as u64))
+    } else {
+        format!("⚠ NODE AHEAD OF REFERENCE: {} blocks", format_number(block_diff.unsigned_abs()))
+    };
+
+    let banner = Paragraph::new(Span::styled(message, Style::default().fg(Color::White).bg(Color::Red).bold()))
+        .style(Style::default().bg(Color::Red))
+        .alignment(Alignment::Center);
+    frame.render_widget(banner, area);
+}
+
+fn draw_alerts(frame: &mut Frame, area: Rect, state: &AppState) {
+    let message = state
+        .alerts
+        .active()
+        .iter()
+        .map(|alert| alert.message.as_str())
+        .collect::<Vec<_>>()
+        .join("  |  ");
+
+    let title = if state.bell_silenced { " ALERT (acknowledged) " } else { " ALERT " };
     let block = Block::default()
+        .title(title)
+        .title_style(Style::default().fg(Color::Red).add_modifier(Modifier::BOLD))
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(label_color));
+        .border_style(Style::default().fg(Color::Red));
+
+    let paragraph = Paragraph::new(Line::from(Span::styled(
+        format!("⚠ {}", message),
+        Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+    )))
+    .block(block);
+    frame.render_widget(paragraph, area);
+}
+
+/// Dim a panel and label its top border with how long its data source has been silent
+fn mark_stale(frame: &mut Frame, area: Rect, state: &AppState, source: DataSource) {
+    let Some(age) = state.stale_for(source) else {
+        return;
+    };
+    if area.height == 0 {
+        return;
+    }
+
+    frame.buffer_mut().set_style(area, Style::default().add_modifier(Modifier::DIM));
+
+    let label = format!(" (stale {}s) ", age.as_secs());
+    let width = (label.len() as u16).min(area.width.saturating_sub(2));
+    let label_area = Rect::new(area.x + area.width.saturating_sub(width + 1), area.y, width, 1);
+    frame.render_widget(Paragraph::new(Span::styled(label, Style::default().fg(Color::Yellow))), label_area);
+}
+
+/// Whether flashing elements are in their lit half of the 1s cycle
+fn flash_on() -> bool {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| (d.as_millis() / 500).is_multiple_of(2))
+        .unwrap_or(true)
+}
+
+/// Five-cell bar filling up as block age approaches the threshold (`ratio` = age / threshold)
+fn block_age_bar(ratio: f64) -> String {
+    const CELLS: usize = 5;
+    let filled = ((ratio * CELLS as f64).ceil().max(0.0) as usize).clamp(1, CELLS);
+    format!("{}{}", "█".repeat(filled), "░".repeat(CELLS - filled))
+}
+
+/// Border color for a panel: flashes red while one of its alerts is firing
+fn alert_border_color(state: &AppState, kinds: &[AlertKind], normal: Color) -> Color {
+    if flash_on() && state.alert_active(kinds) {
+        Color::Red
+    } else {
+        normal
+    }
+}
+
+fn draw_sync_progress(frame: &mut Frame, area: Rect, state: &AppState, label_color: Color, value_color: Color, gauge_color: Color) {
+    let block = Block::default()
+        .title(" STATESYNC ")
+        .title_style(Style::default().fg(label_color))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(alert_border_color(state, &[AlertKind::SyncBehind], label_color)));
+
+    // sync_percentage() is 100 when there is no target yet
+    let pct = state.metrics.sync_percentage();
+    let label = format!(
+        "{:.2}%  ({} / {})",
+        pct,
+        format_number(state.metrics.statesync_progress),
+        format_number(state.metrics.statesync_target),
+    );
+
+    let gauge = Gauge::default()
+        .block(block)
+        .gauge_style(Style::default().fg(gauge_color))
+        .ratio((pct / 100.0).clamp(0.0, 1.0))
+        .label(Span::styled(label, Style::default().fg(value_color).bold()));
+
+    frame.render_widget(gauge, area);
+}
+
+fn draw_secondary_stats(frame: &mut Frame, area: Rect, lines: Vec<Line<'static>>, border_color: Color) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(border_color));
 
     let inner = block.inner(area);
     frame.render_widget(block, area);
 
-    // Build stats line
+    frame.render_widget(Paragraph::new(lines), inner);
+}
+
+/// Build the secondary stats segments and wrap them into lines that fit `width`
+fn secondary_stats_lines(state: &AppState, label_color: Color, value_color: Color, width: u16) -> Vec<Line<'static>> {
     let sys = &state.system;
+    let thresholds = &state.config.thresholds;
+    let symbols = state.theme.uses_symbols();
 
     // CPU usage
     let cpu_color = if sys.cpu_usage_pct < 50.0 {
@@ -339,6 +871,13 @@ fn draw_secondary_stats(frame: &mut Frame, area: Rect, state: &AppState, label_c
         Color::Red
     };
 
+    // Per-core usage as one bar character per core
+    let mut cores_spans = vec![Span::styled("CORES: ", Style::default().fg(label_color))];
+    cores_spans.extend(sys.cpu_cores_pct.iter().map(|&pct| {
+        let level = ((pct / 100.0) * 7.0).round().clamp(0.0, 7.0) as usize;
+        Span::styled(CORE_BARS[level].to_string(), Style::default().fg(usage_color(pct)))
+    }));
+
     // Memory usage
     let mem_color = if sys.memory_used_pct < 50.0 {
         Color::Green
@@ -348,6 +887,18 @@ fn draw_secondary_stats(frame: &mut Frame, area: Rect, state: &AppState, label_c
         Color::Red
     };
 
+    // Node process RSS next to system memory, when the primary service is running
+    // (memory is 1024-based, unlike the decimal throughput rates)
+    let mem_detail = match state.config.services.first() {
+        Some(unit) if sys.process_rss_gb > 0.0 => format!(
+            " ({:.0}Gi, {} {:.1}Gi)",
+            sys.memory_used_gb,
+            unit.trim_start_matches("monad-"),
+            sys.process_rss_gb
+        ),
+        _ => format!(" ({:.0}Gi)", sys.memory_used_gb),
+    };
+
     // Disk usage
     let disk_color = if sys.disk_used_pct < 50.0 {
         Color::Green
@@ -357,10 +908,19 @@ fn draw_secondary_stats(frame: &mut Frame, area: Rect, state: &AppState, label_c
         Color::Red
     };
 
-    // Services status
-    let services_ok = sys.all_services_running();
-    let services_color = if services_ok { Color::Green } else { Color::Red };
-    let services_str = if services_ok { "✓" } else { "✗" };
+    // Services status, one indicator per unit so a down service is obvious at a glance
+    let services_label_color = if !sys.services_available || sys.all_services_running() { label_color } else { Color::Red };
+    let mut services_spans = vec![Span::styled("SVC:", Style::default().fg(services_label_color))];
+    if !sys.services_available {
+        services_spans.push(Span::styled(" n/a", Style::default().fg(label_color).add_modifier(Modifier::DIM)));
+    }
+    for (unit, active) in &sys.services {
+        let (mark, color) = if *active { ("✓", Color::Green) } else { ("✗", Color::Red) };
+        services_spans.push(Span::styled(
+            format!(" {}{}", service_short_name(unit), mark),
+            Style::default().fg(color),
+        ));
+    }
 
     // Network bandwidth
     let net_rx = AppState::format_bandwidth(state.net_rx_rate);
@@ -370,33 +930,301 @@ fn draw_secondary_stats(frame: &mut Frame, area: Rect, state: &AppState, label_c
     let fin_lag = sys.finalized_lag();
     let lag_color = if fin_lag <= 3 { Color::Green } else if fin_lag <= 10 { Color::Yellow } else { Color::Red };
 
-    let stats = Line::from(vec![
-        Span::styled("CPU: ", Style::default().fg(label_color)),
-        Span::styled(format!("{:.0}%", sys.cpu_usage_pct), Style::default().fg(cpu_color)),
-        Span::raw("  |  "),
-        Span::styled("MEM: ", Style::default().fg(label_color)),
-        Span::styled(format!("{:.0}%", sys.memory_used_pct), Style::default().fg(mem_color)),
-        Span::styled(format!(" ({:.0}G)", sys.memory_used_gb), Style::default().fg(label_color)),
-        Span::raw("  |  "),
-        Span::styled("DISK: ", Style::default().fg(label_color)),
-        Span::styled(format!("{:.0}%", sys.disk_used_pct), Style::default().fg(disk_color)),
-        Span::raw("  |  "),
-        Span::styled("NET: ", Style::default().fg(label_color)),
-        Span::styled(format!("↓{} ↑{}", net_rx, net_tx), Style::default().fg(value_color)),
-        Span::raw("  |  "),
-        Span::styled("SVC: ", Style::default().fg(label_color)),
-        Span::styled(services_str, Style::default().fg(services_color)),
-        Span::raw("  |  "),
-        Span::styled("FIN: ", Style::default().fg(label_color)),
-        Span::styled(format!("-{}", fin_lag), Style::default().fg(lag_color)),
-    ]);
+    // Verified vs finalized gap
+    let ver_gap = sys.verified_finalized_gap();
+    let ver_color = if ver_gap <= thresholds.verified_gap_warn {
+        Color::Green
+    } else if ver_gap <= thresholds.verified_gap_crit {
+        Color::Yellow
+    } else {
+        Color::Red
+    };
+
+    // monad-mpt failures show as n/a rather than a healthy-looking 0%
+    let (disk_value, fin_value, ver_value) = if sys.disk_data_available {
+        (
+            status_span(format!("{:.0}%", sys.disk_used_pct), disk_color, symbols),
+            status_span(format!("-{}", fin_lag), lag_color, symbols),
+            status_span(format!("+{}", ver_gap), ver_color, symbols),
+        )
+    } else {
+        (
+            Span::styled("n/a", Style::default().fg(label_color).add_modifier(Modifier::DIM)),
+            Span::styled("n/a", Style::default().fg(label_color).add_modifier(Modifier::DIM)),
+            Span::styled("n/a", Style::default().fg(label_color).add_modifier(Modifier::DIM)),
+        )
+    };
+
+    // Retained history window; a short window means aggressive pruning
+    let history_spans = if sys.disk_data_available {
+        let history_color = if sys.history_count < thresholds.history_min_blocks { Color::Yellow } else { Color::Green };
+        vec![
+            Span::styled("HIST: ", Style::default().fg(label_color)),
+            status_span(format!("{} blocks", format_number(sys.history_count)), history_color, symbols),
+            Span::styled(
+                format!(" ({}–{})", format_number(sys.history_earliest), format_number(sys.history_latest)),
+                Style::default().fg(label_color),
+            ),
+        ]
+    } else {
+        vec![
+            Span::styled("HIST: ", Style::default().fg(label_color)),
+            Span::styled("n/a", Style::default().fg(label_color).add_modifier(Modifier::DIM)),
+        ]
+    };
+
+    // Load average relative to core count: above 1.0 per core means work is queueing
+    let load_per_core = sys.load_1 / sys.cpu_count.max(1) as f64;
+    let load_color = if load_per_core < 0.7 {
+        Color::Green
+    } else if load_per_core < 1.0 {
+        Color::Yellow
+    } else {
+        Color::Red
+    };
+
+    // Upstream validators
+    let validators = state.metrics.upstream_validators;
+    let validators_color = if validators < thresholds.validators_crit {
+        Color::Red
+    } else if validators < thresholds.validators_warn {
+        Color::Yellow
+    } else {
+        Color::Green
+    };
+
+    // Losing upstream validators is an early connectivity warning
+    let (validators_arrow, validators_arrow_color) = match state.validators_trend() {
+        1 => (" ▲", Color::Green),
+        -1 => (" ▼", Color::Red),
+        _ => ("", label_color),
+    };
+
+    // Pending transaction pool
+    let pending = state.metrics.pending_txs;
+    let pool_color = if pending > thresholds.pool_crit {
+        Color::Red
+    } else if pending > thresholds.pool_warn {
+        Color::Yellow
+    } else {
+        Color::Green
+    };
+
+    // For the pool: growing backlog = bad (red), draining = good (green)
+    let (pool_arrow, pool_arrow_color) = match state.pending_trend() {
+        1 => (" ▲", Color::Red),
+        -1 => (" ▼", Color::Green),
+        _ => ("", label_color),
+    };
+
+    let mut segments = vec![
+        vec![
+            Span::styled("CPU: ", Style::default().fg(label_color)),
+            if sys.cpu_measured {
+                status_span(format!("{:.0}%", sys.cpu_usage_pct), cpu_color, symbols)
+            } else {
+                Span::styled("measuring", Style::default().fg(label_color).add_modifier(Modifier::DIM))
+            },
+        ],
+        cores_spans,
+        vec![
+            Span::styled("LOAD: ", Style::default().fg(label_color)),
+            status_span(format!("{:.1}", sys.load_1), load_color, symbols),
+            Span::styled(format!(" {:.1} {:.1}", sys.load_5, sys.load_15), Style::default().fg(value_color)),
+        ],
+        vec![
+            Span::styled("MEM: ", Style::default().fg(label_color)),
+            status_span(format!("{:.0}%", sys.memory_used_pct), mem_color, symbols),
+            Span::styled(mem_detail, Style::default().fg(label_color)),
+        ],
+        vec![
+            Span::styled("DISK: ", Style::default().fg(label_color)),
+            disk_value,
+        ],
+        vec![
+            Span::styled("IO: ", Style::default().fg(label_color)),
+            Span::styled(
+                format!(
+                    "r{} w{}",
+                    AppState::format_bandwidth(sys.disk_read_rate),
+                    AppState::format_bandwidth(sys.disk_write_rate)
+                ),
+                Style::default().fg(value_color),
+            ),
+        ],
+        vec![
+            Span::styled("NET: ", Style::default().fg(label_color)),
+            Span::styled(format!("↓{} ↑{}", net_rx, net_tx), Style::default().fg(value_color)),
+        ],
+        services_spans,
+        vec![
+            Span::styled("BLOCK TIME: ", Style::default().fg(label_color)),
+            match state.avg_block_time() {
+                Some(secs) => Span::styled(format!("{:.2}s", secs), Style::default().fg(value_color)),
+                None => Span::styled("...", Style::default().fg(label_color).add_modifier(Modifier::DIM)),
+            },
+        ],
+        vec![
+            Span::styled("FIN: ", Style::default().fg(label_color)),
+            fin_value,
+        ],
+        vec![
+            Span::styled("VER: ", Style::default().fg(label_color)),
+            ver_value,
+        ],
+        history_spans,
+        vec![
+            Span::styled("VAL: ", Style::default().fg(label_color)),
+            status_span(validators.to_string(), validators_color, symbols),
+            Span::styled(validators_arrow, Style::default().fg(validators_arrow_color)),
+        ],
+        vec![
+            Span::styled("POOL: ", Style::default().fg(label_color)),
+            status_span(format_number(pending), pool_color, symbols),
+            Span::styled(pool_arrow, Style::default().fg(pool_arrow_color)),
+        ],
+    ];
+
+    // Consensus round / view, only from exporters that report them
+    if state.metrics.consensus_round > 0 {
+        segments.push(vec![
+            Span::styled("BFT: ", Style::default().fg(label_color)),
+            Span::styled(
+                format!(
+                    "round {} view {}",
+                    format_number(state.metrics.consensus_round),
+                    format_number(state.metrics.consensus_view)
+                ),
+                Style::default().fg(value_color),
+            ),
+        ]);
+    }
+
+    // Swap, only when the machine has any configured; any real use on a node is suspect
+    if sys.swap_total_gb > 0.0 {
+        let swap_color = if sys.swap_used_pct > thresholds.swap_crit_pct {
+            Color::Red
+        } else if sys.swap_used_pct >= 1.0 {
+            Color::Yellow
+        } else {
+            Color::Green
+        };
+        segments.insert(3, vec![
+            Span::styled("SWAP: ", Style::default().fg(label_color)),
+            status_span(format!("{:.0}%", sys.swap_used_pct), swap_color, symbols),
+            Span::styled(format!(" ({:.1}Gi)", sys.swap_used_gb), Style::default().fg(label_color)),
+        ]);
+    }
+
+    wrap_segments(segments, width)
+}
+
+/// Marker conveying what a status color means, for readers who can't tell the colors apart
+fn status_symbol(color: Color) -> &'static str {
+    match color {
+        Color::Green => "✓",
+        Color::Yellow => "!",
+        Color::Red => "✗",
+        _ => "",
+    }
+}
+
+/// A health-colored value, prefixed with its status symbol when the theme uses symbols
+fn status_span(text: String, color: Color, symbols: bool) -> Span<'static> {
+    let text = if symbols { format!("{}{}", status_symbol(color), text) } else { text };
+    Span::styled(text, Style::default().fg(color))
+}
+
+/// Short uppercase label for a unit: "monad-execution" -> "EXE"
+fn service_short_name(unit: &str) -> String {
+    unit.trim_start_matches("monad-").chars().take(3).collect::<String>().to_uppercase()
+}
+
+/// Bar characters for per-core usage, 0% to 100%
+const CORE_BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Green / yellow / red for a usage percentage
+fn usage_color(pct: f64) -> Color {
+    if pct < 50.0 {
+        Color::Green
+    } else if pct < 80.0 {
+        Color::Yellow
+    } else {
+        Color::Red
+    }
+}
 
-    frame.render_widget(Paragraph::new(stats), inner);
+/// Join segments with separators, starting a new line whenever the next one would overflow
+fn wrap_segments(segments: Vec<Vec<Span<'static>>>, width: u16) -> Vec<Line<'static>> {
+    const SEPARATOR: &str = "  |  ";
+
+    let width = width as usize;
+    let mut lines: Vec<Line> = Vec::new();
+    let mut current: Vec<Span> = Vec::new();
+    let mut current_width = 0;
+
+    for segment in segments {
+        let segment_width: usize = segment.iter().map(|s| s.width()).sum();
+        if !current.is_empty() && current_width + SEPARATOR.len() + segment_width > width {
+            lines.push(Line::from(std::mem::take(&mut current)));
+            current_width = 0;
+        }
+        if !current.is_empty() {
+            current.push(Span::raw(SEPARATOR));
+            current_width += SEPARATOR.len();
+        }
+        current_width += segment_width;
+        current.extend(segment);
+    }
+
+    if !current.is_empty() {
+        lines.push(Line::from(current));
+    }
+
+    lines
 }
 
 fn draw_sparkline(frame: &mut Frame, area: Rect, state: &AppState, label_color: Color, sparkline_color: Color) {
+    // Network shows RX and TX side by side; rates only change every system refresh (5s)
+    if state.graph_metric == GraphMetric::Network {
+        let halves = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(area);
+        let rx: Vec<u64> = state.net_rx_history.iter().copied().collect();
+        let tx: Vec<u64> = state.net_tx_history.iter().copied().collect();
+        // Peak over the graph's 10 minutes, so a burst stays readable after it scrolls by
+        let title = |name: &str, samples: &[u64]| match samples.iter().max() {
+            Some(&peak) => format!("{} (5s samples)  peak: {}", name, AppState::format_bandwidth(peak as f64)),
+            None => format!("{} (5s samples)", name),
+        };
+        render_sparkline(frame, halves[0], &title("NET ↓ RX", &rx), rx, None, label_color, sparkline_color);
+        render_sparkline(frame, halves[1], &title("NET ↑ TX", &tx), tx, None, label_color, sparkline_color);
+        return;
+    }
+
+    // The header has room for the peak only; the TPS graph adds the window's average and low
+    let title = match (state.graph_metric, state.tps_avg(), state.tps_min()) {
+        (GraphMetric::Tps, Some(avg), Some(min)) => {
+            let span = state.tps_window_span().map(|d| format!(" (last {})", format_span(d.as_secs()))).unwrap_or_default();
+            format!("{}{}  avg: {}  min: {}", state.graph_name(), span, format_number(avg.round() as u64), format_number(min))
+        }
+        _ => state.graph_name().to_string(),
+    };
+    render_sparkline(frame, area, &title, state.graph_data(), state.graph_max(), label_color, sparkline_color);
+}
+
+fn render_sparkline(
+    frame: &mut Frame,
+    area: Rect,
+    title: &str,
+    raw_data: Vec<u64>,
+    max: Option<u64>,
+    label_color: Color,
+    sparkline_color: Color,
+) {
     let block = Block::default()
-        .title(" TPS ")
+        .title(format!(" {} ", title))
         .title_style(Style::default().fg(label_color))
         .borders(Borders::ALL)
         .border_style(Style::default().fg(label_color));
@@ -404,26 +1232,29 @@ fn draw_sparkline(frame: &mut Frame, area: Rect, state: &AppState, label_color:
     // Calculate available width (subtract 2 for borders)
     let available_width = area.width.saturating_sub(2) as usize;
 
-    // Get data and pad left with zeros to fill width (right-align the graph)
-    let raw_data = state.tps_sparkline_data();
+    // Pad left with zeros to fill width (right-align the graph)
     let raw_len = raw_data.len();
     let data: Vec<u64> = if raw_len < available_width {
         let padding = available_width - raw_len;
-        std::iter::repeat(0).take(padding).chain(raw_data).collect()
+        std::iter::repeat_n(0, padding).chain(raw_data).collect()
     } else {
         raw_data.into_iter().skip(raw_len - available_width).collect()
     };
 
-    let sparkline = Sparkline::default()
+    let mut sparkline = Sparkline::default()
         .block(block)
         .data(&data)
         .style(Style::default().fg(sparkline_color))
         .bar_set(symbols::bar::NINE_LEVELS);
+    if let Some(max) = max {
+        sparkline = sparkline.max(max);
+    }
 
     frame.render_widget(sparkline, area);
 }
 
-fn draw_blocks(frame: &mut Frame, area: Rect, state: &AppState, label_color: Color, text_dim: Color) {
+/// Returns the area covered by block rows (one row per block, newest first) for mouse hit-testing
+fn draw_blocks(frame: &mut Frame, area: Rect, state: &AppState, label_color: Color, text_dim: Color) -> Rect {
     // Split area for Christmas tree if theme is active
     let (blocks_area, tree_area) = if state.theme == Theme::Christmas && area.width > 80 {
         let chunks = Layout::default()
@@ -458,6 +1289,7 @@ fn draw_blocks(frame: &mut Frame, area: Rect, state: &AppState, label_color: Col
 
     let all_blocks = state.recent_blocks();
     let blocks_to_show = &all_blocks[..all_blocks.len().min(available_rows)];
+    let rows_area = Rect::new(inner.x, inner.y + 1, inner.width, blocks_to_show.len() as u16);
 
     let now_ts = std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
@@ -466,7 +1298,8 @@ fn draw_blocks(frame: &mut Frame, area: Rect, state: &AppState, label_color: Col
 
     let rows: Vec<Row> = blocks_to_show
         .iter()
-        .map(|b| {
+        .enumerate()
+        .map(|(i, b)| {
             let hash_display = if wide_mode {
                 b.hash.clone()
             } else if b.hash.len() > 14 {
@@ -475,13 +1308,28 @@ fn draw_blocks(frame: &mut Frame, area: Rect, state: &AppState, label_color: Col
                 b.hash.clone()
             };
 
-            let age = if b.timestamp > 0 && now_ts >= b.timestamp {
+            let age = if state.absolute_time && b.timestamp > 0 {
+                format_clock(b.timestamp)
+            } else if b.timestamp > 0 && now_ts >= b.timestamp {
                 let secs = now_ts - b.timestamp;
                 format!("{}s ago", secs)
             } else {
                 "...".to_string()
             };
 
+            // Blocks are newest-first, so the parent is the next entry
+            let gap = all_blocks
+                .get(i + 1)
+                .filter(|parent| parent.timestamp > 0 && b.timestamp >= parent.timestamp)
+                .map(|parent| b.timestamp - parent.timestamp);
+            let gap_cell = match gap {
+                Some(secs) if secs > state.config.thresholds.block_gap_crit_secs => {
+                    Cell::from(format!("+{}s", secs)).style(Style::default().fg(Color::Red))
+                }
+                Some(secs) => Cell::from(format!("+{}s", secs)),
+                None => Cell::from("..."),
+            };
+
             let gas_pct = if b.gas_limit > 0 {
                 (b.gas_used as f64 / b.gas_limit as f64) * 100.0
             } else {
@@ -498,13 +1346,18 @@ fn draw_blocks(frame: &mut Frame, area: Rect, state: &AppState, label_color: Col
             let gas_bar = format!("{}{}{}", "█".repeat(filled), pct_str, "░".repeat(empty));
 
             Row::new(vec![
-                format!("#{}", format_number(b.number)),
-                format!("{} txs", b.tx_count),
-                hash_display,
-                gas_bar,
-                age,
+                Cell::from(format!("#{}", format_number(b.number))),
+                Cell::from(format!("{} txs", b.tx_count)),
+                Cell::from(hash_display),
+                Cell::from(gas_bar),
+                gap_cell,
+                Cell::from(age),
             ])
-            .style(Style::default().fg(text_dim))
+            .style(if state.selected_block == Some(b.number) {
+                Style::default().fg(text_dim).add_modifier(Modifier::REVERSED)
+            } else {
+                Style::default().fg(text_dim)
+            })
         })
         .collect();
 
@@ -513,17 +1366,184 @@ fn draw_blocks(frame: &mut Frame, area: Rect, state: &AppState, label_color: Col
         Constraint::Length(10),
         Constraint::Length(hash_width),
         Constraint::Length(9),  // Gas bar with % overlay
+        Constraint::Length(5),
         Constraint::Length(10),
     ];
 
     let table = Table::new(rows, widths)
         .header(
-            Row::new(vec!["BLOCK", "TXS", "HASH", "GAS", "AGE"])
+            Row::new(vec!["BLOCK", "TXS", "HASH", "GAS", "GAP", if state.absolute_time { "TIME UTC" } else { "AGE" }])
                 .style(Style::default().fg(label_color).add_modifier(Modifier::BOLD)),
         )
         .column_spacing(2);
 
     frame.render_widget(table, inner);
+    rows_area
+}
+
+fn draw_block_detail(frame: &mut Frame, area: Rect, state: &AppState, title_color: Color, label_color: Color, value_color: Color) {
+    let Some(b) = state.selected_block() else {
+        return;
+    };
+
+    let now_ts = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let age_secs = now_ts.checked_sub(b.timestamp).filter(|_| b.timestamp > 0);
+
+    // Header subscriptions report 0 txs until the follow-up eth_getBlockByNumber lands
+    let tx_count = if b.tx_count == 0 && age_secs.is_some_and(|s| s < 5) {
+        "pending".to_string()
+    } else {
+        b.tx_count.to_string()
+    };
+
+    let gas_pct = if b.gas_limit > 0 {
+        (b.gas_used as f64 / b.gas_limit as f64) * 100.0
+    } else {
+        0.0
+    };
+
+    let age = age_secs
+        .map(|s| format!("{}s ago", s))
+        .unwrap_or_else(|| "...".to_string());
+
+    let field = |label: &'static str, value: String| {
+        Line::from(vec![
+            Span::styled(format!("{:<11}", label), Style::default().fg(label_color)),
+            Span::styled(value, Style::default().fg(value_color)),
+        ])
+    };
+
+    let lines = vec![
+        field("NUMBER", format!("#{}", format_number(b.number))),
+        field("HASH", b.hash.clone()),
+        field("TXS", tx_count),
+        field("GAS USED", format!("{} / {} ({:.1}%)", format_number(b.gas_used), format_number(b.gas_limit), gas_pct)),
+        field("TIMESTAMP", b.timestamp.to_string()),
+        field("AGE", age),
+    ];
+
+    // Hash is 66 chars, plus label column and borders
+    let width = 81.min(area.width);
+    let height = (lines.len() as u16 + 2).min(area.height);
+    let popup = Rect::new(
+        area.x + (area.width - width) / 2,
+        area.y + (area.height - height) / 2,
+        width,
+        height,
+    );
+
+    let block = Block::default()
+        .title(Span::styled(" BLOCK DETAIL ", Style::default().fg(title_color).bold()))
+        .title_bottom(Line::from(Span::styled(" enter/esc: close ", Style::default().fg(label_color))))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(label_color));
+
+    frame.render_widget(Clear, popup);
+    frame.render_widget(Paragraph::new(lines).block(block), popup);
+}
+
+fn draw_error_log(frame: &mut Frame, area: Rect, state: &AppState, title_color: Color, label_color: Color, value_color: Color) {
+    let width = 100.min(area.width);
+    let height = 20.min(area.height);
+    let popup = Rect::new(
+        area.x + (area.width - width) / 2,
+        area.y + (area.height - height) / 2,
+        width,
+        height,
+    );
+
+    // Newest first, as many as fit inside the borders
+    let lines: Vec<Line> = if state.error_log.is_empty() {
+        vec![Line::from(Span::styled("no errors", Style::default().fg(label_color)))]
+    } else {
+        state
+            .error_log
+            .iter()
+            .rev()
+            .take(height.saturating_sub(2) as usize)
+            .map(|(at, error)| {
+                Line::from(vec![
+                    Span::styled(format!("{:>8} ", format!("{}s ago", at.elapsed().as_secs())), Style::default().fg(label_color)),
+                    Span::styled(error.clone(), Style::default().fg(value_color)),
+                ])
+            })
+            .collect()
+    };
+
+    let block = Block::default()
+        .title(Span::styled(" ERROR LOG ", Style::default().fg(title_color).bold()))
+        .title_bottom(Line::from(Span::styled(" e/esc: close ", Style::default().fg(label_color))))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(label_color));
+
+    frame.render_widget(Clear, popup);
+    frame.render_widget(Paragraph::new(lines).block(block), popup);
+}
+
+fn draw_help(frame: &mut Frame, area: Rect, state: &AppState, title_color: Color, label_color: Color, value_color: Color) {
+    const KEYS: [(&str, &str); 17] = [
+        ("t", "cycle theme"),
+        ("1-9, 0", "pick a theme"),
+        ("g", "cycle graph"),
+        ("c", "compact layout"),
+        ("n / tab", "next node (⇧tab back)"),
+        ("s", "two nodes side by side"),
+        ("↑↓ / j k", "select block"),
+        ("enter", "block details"),
+        ("y", "copy block hash"),
+        ("w", "block age / clock time"),
+        ("p", "10m / all-time peak TPS"),
+        ("r", "refresh now"),
+        ("+ / -", "faster / slower refresh"),
+        ("e", "error log"),
+        ("a", "acknowledge alerts"),
+        ("?", "this help"),
+        ("q / esc", "quit"),
+    ];
+
+    let mut lines: Vec<Line> = KEYS
+        .iter()
+        .map(|(key, action)| {
+            Line::from(vec![
+                Span::styled(format!("{:<10}", key), Style::default().fg(value_color)),
+                Span::styled(*action, Style::default().fg(label_color)),
+            ])
+        })
+        .collect();
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled("THEMES", Style::default().fg(title_color).bold())));
+    for (i, theme) in Theme::ALL.iter().enumerate() {
+        if *theme == Theme::Custom && state.config.custom_theme.is_none() {
+            continue;
+        }
+        let marker = if *theme == state.theme { "●" } else { " " };
+        lines.push(Line::from(vec![
+            Span::styled(format!("{:<2}{} ", (i + 1) % 10, marker), Style::default().fg(value_color)),
+            Span::styled(theme.name(), Style::default().fg(label_color)),
+        ]));
+    }
+
+    let width = 40.min(area.width);
+    let height = (lines.len() as u16 + 2).min(area.height);
+    let popup = Rect::new(
+        area.x + (area.width - width) / 2,
+        area.y + (area.height - height) / 2,
+        width,
+        height,
+    );
+
+    let block = Block::default()
+        .title(Span::styled(" HELP ", Style::default().fg(title_color).bold()))
+        .title_bottom(Line::from(Span::styled(" ?/esc: close ", Style::default().fg(label_color))))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(label_color));
+
+    frame.render_widget(Clear, popup);
+    frame.render_widget(Paragraph::new(lines).block(block), popup);
 }
 
 fn draw_christmas_tree(frame: &mut Frame, area: Rect, _state: &AppState, label_color: Color) {
@@ -536,6 +1556,9 @@ fn draw_christmas_tree(frame: &mut Frame, area: Rect, _state: &AppState, label_c
 
     let width = inner.width as usize;
     let height = inner.height as usize;
+    if width == 0 || height == 0 {
+        return;
+    }
 
     // Use time for animations - updates every 800ms for very subtle snow
     let tick = (std::time::SystemTime::now()
@@ -556,7 +1579,6 @@ fn draw_christmas_tree(frame: &mut Frame, area: Rect, _state: &AppState, label_c
     let star_color = Color::Rgb(255, 215, 0);
     let trunk_color = Color::Rgb(139, 69, 19);
     let snow_color = Color::Rgb(200, 220, 255);
-    let text_color = Color::Rgb(255, 250, 250);
 
     // Tree pattern (will be centered)
     let tree_pattern = [
@@ -599,7 +1621,7 @@ fn draw_christmas_tree(frame: &mut Frame, area: Rect, _state: &AppState, label_c
                 let ch = greeting.chars().nth(char_idx).unwrap_or(' ');
                 let festive_red = Color::Rgb(255, 50, 50);
                 let festive_green = Color::Rgb(50, 205, 50);
-                let color = if char_idx % 2 == 0 { festive_red } else { festive_green };
+                let color = if char_idx.is_multiple_of(2) { festive_red } else { festive_green };
                 spans.push(Span::styled(
                     ch.to_string(),
                     Style::default().fg(color).bold(),
@@ -681,7 +1703,8 @@ fn get_snowflake(row: usize, col: usize, tick: usize) -> String {
     flakes[idx].to_string()
 }
 
-fn draw_footer(frame: &mut Frame, area: Rect, state: &AppState, label_color: Color, value_color: Color) {
+/// Returns the area of the `[theme]` label, which cycles the theme when clicked
+fn draw_footer(frame: &mut Frame, area: Rect, state: &AppState, label_color: Color, value_color: Color) -> Rect {
     let block = Block::default()
         .borders(Borders::ALL)
         .border_style(Style::default().fg(label_color));
@@ -703,8 +1726,17 @@ fn draw_footer(frame: &mut Frame, area: Rect, state: &AppState, label_color: Col
     };
 
     // Error or status
-    let status = if let Some(ref err) = state.last_error {
+    let status = if state.confirming_quit {
+        Span::styled("Quit? (y/n)", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD))
+    } else if let Some(ref err) = state.last_error {
         Span::styled(format!("⚠ {}", err), Style::default().fg(Color::Red))
+    } else if state.metrics_frozen() {
+        Span::styled("⚠ metrics frozen: exporter timestamp not advancing", Style::default().fg(Color::Yellow))
+    } else if let Some(lag) = state.metrics_sample_lag() {
+        let message = format!("⚠ metrics stale: exporter samples {}s behind the clock", lag.as_secs());
+        Span::styled(message, Style::default().fg(Color::Yellow))
+    } else if let Some(notice) = state.notice() {
+        Span::styled(format!("ℹ {}", notice), Style::default().fg(Color::Yellow))
     } else {
         let time_since = state
             .time_since_last_block()
@@ -713,22 +1745,32 @@ fn draw_footer(frame: &mut Frame, area: Rect, state: &AppState, label_color: Col
         Span::styled(format!("last: {}", time_since), Style::default().fg(label_color))
     };
 
-    let footer = Line::from(vec![
+    let mut spans = vec![
         Span::styled("UP: ", Style::default().fg(label_color)),
         Span::styled(service_uptime, Style::default().fg(value_color)),
         Span::raw("  |  "),
         Span::styled("GAS: ", Style::default().fg(label_color)),
         Span::styled(format!("{:.0}gwei", gas_gwei), Style::default().fg(value_color)),
         Span::raw("  |  "),
+        Span::styled("REFRESH: ", Style::default().fg(label_color)),
+        Span::styled(
+            format!("{}/{}", format_interval(state.refresh.metrics), format_interval(state.refresh.system)),
+            Style::default().fg(value_color),
+        ),
+        Span::raw("  |  "),
         Span::styled(version, Style::default().fg(label_color)),
         Span::raw("  |  "),
         status,
         Span::raw("  |  "),
-        Span::styled(format!("[{}] ", state.theme_name()), Style::default().fg(value_color)),
-        Span::styled("t: theme  q: quit", Style::default().fg(label_color)),
-    ]);
-
-    frame.render_widget(Paragraph::new(footer), inner);
+    ];
+    let theme_x = spans.iter().map(|s| s.width()).sum::<usize>() as u16;
+    let theme_span = Span::styled(format!("[{}]", state.theme_name()), Style::default().fg(value_color));
+    let theme_label = Rect::new(inner.x.saturating_add(theme_x), inner.y, theme_span.width() as u16, 1).intersection(inner);
+    spans.push(theme_span);
+    spans.push(Span::styled(" t: theme  g: graph  c: compact  ↑↓/enter: block  e: errors  a: ack  ?: help  q: quit", Style::default().fg(label_color)));
+
+    frame.render_widget(Paragraph::new(Line::from(spans)), inner);
+    theme_label
 }
 
 fn format_number(n: u64) -> String {
@@ -742,3 +1784,187 @@ fn format_number(n: u64) -> String {
     }
     result
 }
+
+/// Refresh interval as "250ms", "1s", "2.5s" or "5m"
+fn format_interval(interval: Duration) -> String {
+    let ms = interval.as_millis() as u64;
+    match ms {
+        ms if ms < 1000 => format!("{}ms", ms),
+        ms if ms % 1000 == 0 => format_span(ms / 1000),
+        ms => format!("{:.1}s", ms as f64 / 1000.0),
+    }
+}
+
+/// Whole seconds as "45s", "4m 30s", "2h 5m"
+fn format_span(secs: u64) -> String {
+    match secs {
+        s if s < 60 => format!("{}s", s),
+        s if s < 3600 && s % 60 == 0 => format!("{}m", s / 60),
+        s if s < 3600 => format!("{}m {}s", s / 60, s % 60),
+        s => format!("{}h {}m", s / 3600, s % 3600 / 60),
+    }
+}
+
+/// Unix timestamp to "HH:MM:SS" UTC
+fn format_clock(epoch_secs: u64) -> String {
+    let secs = epoch_secs % 86_400;
+    format!("{:02}:{:02}:{:02}", secs / 3600, secs % 3600 / 60, secs % 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::system::SystemData;
+
+    #[test]
+    fn test_short_node_id() {
+        assert_eq!(short_node_id(""), "...");
+        assert_eq!(short_node_id("validator-2"), "validator-2");
+        assert_eq!(short_node_id("monad-validator-eu-01"), "01");
+        // Cut by characters, not bytes, when there's no usable last segment
+        assert_eq!(short_node_id("münchenvalidatorberlin"), "münchenvalid");
+        assert_eq!(short_node_id("münchen-validator-"), "münchen-vali");
+    }
+
+    #[test]
+    fn test_draw_header_multibyte_node_name() {
+        use crate::config::Config;
+        use ratatui::{backend::TestBackend, Terminal};
+
+        let mut state = AppState::new(Config::default());
+        // "ü" straddles byte 12
+        state.system.node_id = "validator-münchen-1".to_string();
+        let mut terminal = Terminal::new(TestBackend::new(100, 5)).unwrap();
+        terminal
+            .draw(|frame| draw_header(frame, frame.area(), &state, Color::White, Color::Gray, Color::White))
+            .unwrap();
+        let screen: String = terminal.backend().buffer().content().iter().map(|c| c.symbol()).collect();
+        assert!(screen.contains("[1]"));
+    }
+
+    #[test]
+    fn test_format_block_difference() {
+        let sys = SystemData { external_block: 1_000, ..Default::default() };
+
+        // Local behind the network
+        assert_eq!(format_block_difference(sys.block_difference(988)), "-12 behind");
+        // Local ahead of the (lagging) reference
+        assert_eq!(format_block_difference(sys.block_difference(1_002)), "+2 ahead");
+        assert_eq!(format_block_difference(sys.block_difference(1_000)), "in sync");
+    }
+
+    #[test]
+    fn test_status_span_symbols() {
+        assert_eq!(status_span("42%".to_string(), Color::Red, true).content, "✗42%");
+        assert_eq!(status_span("42%".to_string(), Color::Yellow, true).content, "!42%");
+        assert_eq!(status_span("42%".to_string(), Color::Green, false).content, "42%");
+    }
+
+    #[test]
+    fn test_block_age_bar() {
+        assert_eq!(block_age_bar(0.0), "█░░░░");
+        assert_eq!(block_age_bar(0.5), "███░░");
+        assert_eq!(block_age_bar(1.0), "█████");
+        assert_eq!(block_age_bar(12.0), "█████");
+    }
+
+    #[test]
+    fn test_format_interval() {
+        assert_eq!(format_interval(Duration::from_millis(250)), "250ms");
+        assert_eq!(format_interval(Duration::from_millis(1000)), "1s");
+        assert_eq!(format_interval(Duration::from_millis(2500)), "2.5s");
+        assert_eq!(format_interval(Duration::from_secs(300)), "5m");
+    }
+
+    #[test]
+    fn test_format_span() {
+        assert_eq!(format_span(45), "45s");
+        assert_eq!(format_span(300), "5m");
+        assert_eq!(format_span(270), "4m 30s");
+        assert_eq!(format_span(7_500), "2h 5m");
+    }
+
+    #[test]
+    fn test_format_clock() {
+        assert_eq!(format_clock(0), "00:00:00");
+        // 2025-11-24 13:05:09 UTC
+        assert_eq!(format_clock(1_763_989_509), "13:05:09");
+    }
+
+    #[test]
+    fn test_draw_small_terminal() {
+        use ratatui::{backend::TestBackend, Terminal};
+
+        let mut state = AppState::new(crate::config::Config::default());
+        for (width, height) in [(1, 1), (30, 10), (MIN_WIDTH, MIN_HEIGHT)] {
+            let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+            terminal.draw(|frame| draw(frame, std::slice::from_mut(&mut state), 0)).unwrap();
+        }
+
+        let mut terminal = Terminal::new(TestBackend::new(40, 5)).unwrap();
+        terminal.draw(|frame| draw(frame, std::slice::from_mut(&mut state), 0)).unwrap();
+        let text: String = terminal.backend().buffer().content().iter().map(|c| c.symbol()).collect();
+        assert!(text.contains("terminal too small"));
+    }
+
+    #[test]
+    fn test_christmas_decorations_tiny_areas() {
+        use ratatui::{backend::TestBackend, Terminal};
+
+        let state = AppState::new(crate::config::Config::default());
+        let mut terminal = Terminal::new(TestBackend::new(40, 12)).unwrap();
+        terminal
+            .draw(|frame| {
+                for (width, height) in [(0, 0), (1, 1), (2, 2), (3, 12), (40, 1), (5, 3), (22, 12)] {
+                    let area = Rect::new(0, 0, width, height);
+                    draw_christmas_tree(frame, area, &state, Color::Gray);
+                    draw_festive_lights(frame, area);
+                }
+                draw_festive_lights(frame, Rect::new(0, 0, 10, 0));
+                draw_festive_lights(frame, Rect::new(0, 0, 0, 10));
+            })
+            .unwrap();
+    }
+
+    #[test]
+    fn test_draw_multiple_nodes() {
+        use crate::config::{Config, NodeConfig};
+        use ratatui::{backend::TestBackend, Terminal};
+
+        let config = Config::default();
+        let node = NodeConfig {
+            name: "validator-2".to_string(),
+            rpc_url: "ws://10.0.0.2:8081".to_string(),
+            metrics_url: "http://10.0.0.2:8889/metrics".to_string(),
+        };
+        let mut nodes = vec![AppState::new(config.clone()), AppState::remote(&config, &node)];
+
+        let mut terminal = Terminal::new(TestBackend::new(100, 40)).unwrap();
+        let screen = |terminal: &Terminal<TestBackend>| -> String {
+            terminal.backend().buffer().content().iter().map(|c| c.symbol()).collect()
+        };
+        terminal.draw(|frame| draw(frame, &mut nodes, 1)).unwrap();
+        assert!(screen(&terminal).contains("NODES"));
+        assert!(screen(&terminal).contains("▶ validator-2"));
+
+        nodes[1].toggle_split_view();
+        terminal.draw(|frame| draw(frame, &mut nodes, 1)).unwrap();
+        assert!(screen(&terminal).contains("COMPARE"));
+        assert!(screen(&terminal).contains("Δ +0 blocks"));
+        // The footer and popups still show in split view
+        nodes[1].confirming_quit = true;
+        terminal.draw(|frame| draw(frame, &mut nodes, 1)).unwrap();
+        assert!(screen(&terminal).contains("Quit? (y/n)"));
+        nodes[1].confirming_quit = false;
+        nodes[1].toggle_help();
+        terminal.draw(|frame| draw(frame, &mut nodes, 1)).unwrap();
+        assert!(screen(&terminal).contains(" HELP "));
+        nodes[1].toggle_help();
+        nodes[1].toggle_split_view();
+
+        nodes[1].toggle_compact();
+        terminal.draw(|frame| draw(frame, &mut nodes, 1)).unwrap();
+        assert!(!screen(&terminal).contains("NODES"));
+        assert!(screen(&terminal).contains("validator-2"));
+    }
+}
@@ -0,0 +1,51 @@
+use reqwest::Client;
+use serde_json::json;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::alerts::{AlertEvent, Transition};
+
+/// Posts alert transitions as JSON to a webhook (Slack, Discord, ...)
+pub struct WebhookClient {
+    client: Client,
+    url: String,
+}
+
+impl WebhookClient {
+    pub fn new(url: &str, timeout: Duration) -> Self {
+        Self {
+            client: Client::builder().timeout(timeout).build().unwrap_or_default(),
+            url: url.to_string(),
+        }
+    }
+
+    /// Fire-and-forget: the request runs in its own task so the UI loop never waits
+    pub fn notify(&self, node_id: &str, event: &AlertEvent) {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        let status = match event.transition {
+            Transition::Fired => "firing",
+            Transition::Resolved => "resolved",
+        };
+        let summary = format!("[{}] {}: {} ({})", node_id, status, event.alert.message, event.alert.kind.name());
+
+        let payload = json!({
+            "node_id": node_id,
+            "condition": event.alert.kind.name(),
+            "status": status,
+            "message": event.alert.message,
+            "value": event.alert.value,
+            "threshold": event.alert.threshold,
+            "timestamp": timestamp,
+            // Slack and Discord incoming webhooks display these fields
+            "text": summary,
+            "content": summary,
+        });
+
+        let request = self.client.post(&self.url).json(&payload);
+        tokio::spawn(async move {
+            let _ = request.send().await;
+        });
+    }
+}